@@ -19,15 +19,31 @@ import toast from "react-hot-toast";
 
 interface CreateHandleProps {
   imageBase64: string;
+  secret: string;
 }
 
+// 合约校验 media_hash 为 data URI 解码后内容的 sha256 (base64 编码)
+const mediaHash = (dataUrl: string) => {
+  const payload = atob(dataUrl.split(",")[1] ?? "");
+  const bytes = Uint8Array.from(payload, (c) => c.charCodeAt(0));
+  return btoa(String.fromCharCode(...sha256.array(bytes)));
+};
+
+// secret 不能由链上公开的图片或 media_hash 推算出来, 每张图片随机生成
+const randomSecret = () =>
+  Array.from(crypto.getRandomValues(new Uint8Array(4)), (byte) =>
+    byte.toString(16).padStart(2, "0"),
+  ).join("");
+
 export function Create() {
   const wallet = useWalletSelector();
   const [images, setImages] = React.useState<ImageListType>([]);
+  // 图片 dataURL -> 随机生成的 secret
+  const [secrets, setSecrets] = React.useState<Record<string, string>>({});
   const maxNumber = 6;
-  const createHandle = ({ imageBase64 }: CreateHandleProps) => {
+  const createHandle = ({ imageBase64, secret }: CreateHandleProps) => {
     const sendTx = async () => {
-      const hash = sha256(imageBase64);
+      const hash = mediaHash(imageBase64);
       if (wallet.accountId) {
         const tx: Transaction = {
           receiverId: CONTRACT_ID,
@@ -42,7 +58,7 @@ export function Create() {
                     media: imageBase64,
                     media_hash: hash,
                   },
-                  secret: hash.slice(-4),
+                  secret,
                   memo: "",
                 },
                 deposit: parseNearAmount("0.00") || "",
@@ -62,6 +78,14 @@ export function Create() {
   };
   const onChange = (imageList: ImageListType) => {
     setImages(imageList as never[]);
+    setSecrets((secrets) =>
+      Object.fromEntries(
+        imageList.map((image) => [
+          image.dataURL!,
+          secrets[image.dataURL!] ?? randomSecret(),
+        ]),
+      ),
+    );
   };
 
   return (
@@ -88,7 +112,7 @@ export function Create() {
                     key="Mint"
                     className="w-fit"
                     color="danger"
-                    shortcut="sha256(-4)"
+                    shortcut="secret"
                     startContent={
                       <MintIcon
                         className={
@@ -97,12 +121,15 @@ export function Create() {
                       />
                     }
                     onClick={() =>
-                      createHandle({ imageBase64: image.dataURL! })
+                      createHandle({
+                        imageBase64: image.dataURL!,
+                        secret: secrets[image.dataURL!]!,
+                      })
                     }
                   >
                     Mint:
                     <span className="text-danger ">
-                      {sha256(image.dataURL!).slice(-4)}
+                      {secrets[image.dataURL!]}
                     </span>
                   </DropdownItem>
                 </DropdownMenu>
@@ -7,6 +7,7 @@ import type { Transaction } from "@near-wallet-selector/core";
 import { useWalletSelector } from "~/contexts/WalletSelectorContext";
 import { CONTRACT_ID } from "~/config/constants";
 import { parseNearAmount } from "near-api-js/lib/utils/format";
+import { providers } from "near-api-js";
 
 import type { BurnHandleProps } from "~/types";
 import React from "react";
@@ -52,13 +53,12 @@ export function Playground() {
             params: {
               methodName: "user_burn",
               args: {
-                account_id: wallet.accountId,
                 secret,
                 token_id,
                 memo: "burn maze nft",
               },
               deposit: parseNearAmount("0.00") || "",
-              gas: "100000000000000",
+              gas: "300000000000000",
             },
           },
         ],
@@ -66,7 +66,12 @@ export function Playground() {
       };
       await wallet
         .signAndSendTransaction(tx)
-        .then(() => {
+        .then((outcome) => {
+          // 猜错时合约返回 `false` 而不是报错
+          if (outcome && providers.getTransactionLastResult(outcome) === false) {
+            toast.error("secret faild: wrong secret");
+            return;
+          }
           toast.success("Successfully burned!");
         })
         .catch((error) => toast.error(`secret faild: ${error}`));
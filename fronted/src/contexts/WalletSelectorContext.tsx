@@ -127,7 +127,7 @@ export const WalletSelectorContextProvider: React.FC<{
 
     const mazesTokensById = () => {
       const result = async () => {
-        const res: MazesTokenProps[] = await viewFunction({
+        const res: { items: MazesTokenProps[] } = await viewFunction({
           receiverId: contract.contractName,
           methodName: "view_nft_by_id",
           args: {
@@ -137,7 +137,9 @@ export const WalletSelectorContextProvider: React.FC<{
           },
         });
 
-        const filterRes = res.filter((item) => item.metadata.media !== null);
+        const filterRes = res.items.filter(
+          (item) => item.metadata.media !== null
+        );
         setMediaes(filterRes);
       };
       result();
@@ -152,7 +154,7 @@ export const WalletSelectorContextProvider: React.FC<{
 
     const mazesTokensById = () => {
       const result = async () => {
-        const res: MazesTokenProps[] = await viewFunction({
+        const res: { items: MazesTokenProps[] } = await viewFunction({
           receiverId: contract.contractName,
           methodName: "view_all_nft",
           args: {
@@ -161,7 +163,9 @@ export const WalletSelectorContextProvider: React.FC<{
           },
         });
 
-        const filterRes = res.filter((item) => item.metadata.media !== null);
+        const filterRes = res.items.filter(
+          (item) => item.metadata.media !== null
+        );
         setAllMediaesData(filterRes);
       };
       result();
@@ -0,0 +1,152 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+use crate::*;
+
+/// 由合约所有者授予的管理角色, 合约所有者默认拥有全部角色
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    // 批量 mint 等发行操作
+    Minter,
+    // 代为转移, 销毁 NFT 及维护索引
+    Moderator,
+    // 管理赏金与合约资金
+    Treasurer,
+    // 暂停合约功能
+    Pauser,
+}
+
+#[near_bindgen]
+impl Contract {
+    // 合约所有者提名新的所有者, 需要对方调用 `accept_owner` 后才会生效
+    pub fn propose_owner(&mut self, new_owner_id: Option<AccountId>) {
+        self.assert_owner();
+        self.pending_owner_id = new_owner_id.clone();
+
+        MazesEventLog::new(MazesEventLogVariant::OwnerProposed(vec![
+            OwnerProposedLog {
+                owner_id: self.owner_id.clone(),
+                new_owner_id,
+            },
+        ]))
+        .emit();
+    }
+
+    // 被提名的账户接受合约所有权
+    pub fn accept_owner(&mut self) {
+        let account_id = env::predecessor_account_id();
+        require!(
+            self.pending_owner_id.as_ref() == Some(&account_id),
+            "Only the proposed owner can accept ownership."
+        );
+        let old_owner_id = std::mem::replace(&mut self.owner_id, account_id.clone());
+        self.tokens.owner_id = account_id.clone();
+        self.pending_owner_id = None;
+
+        MazesEventLog::new(MazesEventLogVariant::OwnerTransferred(vec![
+            OwnerTransferredLog {
+                old_owner_id,
+                new_owner_id: account_id,
+            },
+        ]))
+        .emit();
+    }
+
+    pub fn grant_role(&mut self, account_id: AccountId, role: Role) {
+        self.assert_owner();
+        let mut roles = self.roles.get(&account_id).cloned().unwrap_or_default();
+        if !roles.insert(role) {
+            return;
+        }
+        self.roles.insert(account_id.clone(), roles);
+
+        MazesEventLog::new(MazesEventLogVariant::RoleGranted(vec![RoleChangedLog {
+            account_id,
+            role,
+        }]))
+        .emit();
+    }
+
+    pub fn revoke_role(&mut self, account_id: AccountId, role: Role) {
+        self.assert_owner();
+        let mut roles = match self.roles.get(&account_id) {
+            Some(roles) if roles.contains(&role) => roles.clone(),
+            _ => return,
+        };
+        roles.remove(&role);
+        if roles.is_empty() {
+            self.roles.remove(&account_id);
+        } else {
+            self.roles.insert(account_id.clone(), roles);
+        }
+
+        MazesEventLog::new(MazesEventLogVariant::RoleRevoked(vec![RoleChangedLog {
+            account_id,
+            role,
+        }]))
+        .emit();
+    }
+
+    pub fn view_owner(&self) -> AccountId {
+        self.owner_id.clone()
+    }
+
+    pub fn view_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner_id.clone()
+    }
+
+    // 查询账户被授予的角色, 不包括合约所有者的默认角色
+    pub fn view_roles(&self, account_id: AccountId) -> Vec<Role> {
+        let mut roles: Vec<Role> = self
+            .roles
+            .get(&account_id)
+            .map(|roles| roles.iter().copied().collect())
+            .unwrap_or_default();
+        roles.sort();
+        roles
+    }
+
+    pub fn has_role(&self, account_id: AccountId, role: Role) -> bool {
+        self.internal_has_role(&account_id, role)
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_has_role(&self, account_id: &AccountId, role: Role) -> bool {
+        account_id == &self.owner_id
+            || self
+                .roles
+                .get(account_id)
+                .is_some_and(|roles| roles.contains(&role))
+    }
+
+    pub(crate) fn assert_owner(&self) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Only contract owner can call this method."
+        );
+    }
+
+    pub(crate) fn assert_role(&self, role: Role) {
+        require!(
+            self.internal_has_role(&env::predecessor_account_id(), role),
+            format!("Only contract owner or {:?} can call this method.", role)
+        );
+    }
+}
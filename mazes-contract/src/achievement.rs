@@ -0,0 +1,177 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U64;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+use crate::*;
+
+// 成就定义的数量上限, 每次 mint 和解谜都会检查全部定义
+pub const MAX_ACHIEVEMENTS: u64 = 50;
+
+/// 获得成就的条件
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AchievementCondition {
+    // 累计解开 `count` 个迷宫
+    Solves { count: u32 },
+    // 累计创建 `count` 个迷宫
+    MazesCreated { count: u32 },
+    // 在迷宫 mint 后 `blocks` 个区块内解开
+    SolvedWithinBlocks { blocks: U64 },
+    // 创建的迷宫存活 `days` 天后才被解开
+    MazeSurvived { days: u32 },
+}
+
+/// 由合约所有者注册的成就
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AchievementDefinition {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub condition: AchievementCondition,
+}
+
+/// 账户获得的成就
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AwardedAchievement {
+    pub id: String,
+    pub block_height: U64,
+}
+
+// 触发成就检查的游戏事件
+pub(crate) enum AchievementTrigger<'a> {
+    // 账户创建了迷宫
+    Minted,
+    // 账户解开了迷宫
+    Solved(&'a MazeState),
+    // 账户创建的迷宫被解开
+    MazeSolved(&'a MazeState),
+}
+
+pub fn default_achievements() -> Vec<AchievementDefinition> {
+    vec![
+        AchievementDefinition {
+            id: "first_solve".to_string(),
+            name: "First Solve".to_string(),
+            description: Some("Solved a maze for the first time.".to_string()),
+            condition: AchievementCondition::Solves { count: 1 },
+        },
+        AchievementDefinition {
+            id: "ten_solves".to_string(),
+            name: "Maze Runner".to_string(),
+            description: Some("Solved 10 mazes.".to_string()),
+            condition: AchievementCondition::Solves { count: 10 },
+        },
+        AchievementDefinition {
+            id: "speed_solver".to_string(),
+            name: "Speed Solver".to_string(),
+            description: Some("Solved a maze within 100 blocks of its mint.".to_string()),
+            condition: AchievementCondition::SolvedWithinBlocks { blocks: U64(100) },
+        },
+        AchievementDefinition {
+            id: "survivor".to_string(),
+            name: "Survivor".to_string(),
+            description: Some("Created a maze that survived 30 days.".to_string()),
+            condition: AchievementCondition::MazeSurvived { days: 30 },
+        },
+    ]
+}
+
+#[near_bindgen]
+impl Contract {
+    // 合约所有者注册新的成就, 已获得的成就不会重新计算
+    pub fn register_achievement(&mut self, definition: AchievementDefinition) {
+        self.assert_owner();
+        require!(!definition.id.is_empty(), "Achievement id is required.");
+        require!(
+            self.achievements
+                .iter()
+                .all(|existing| existing.id != definition.id),
+            "Achievement already exists."
+        );
+        require!(
+            self.achievements.len() < MAX_ACHIEVEMENTS,
+            format!("Cannot have more than {} achievements.", MAX_ACHIEVEMENTS)
+        );
+        self.achievements.push(&definition);
+    }
+
+    pub fn view_achievement_definitions(&self) -> Vec<AchievementDefinition> {
+        self.achievements.to_vec()
+    }
+
+    pub fn view_achievements(&self, account_id: AccountId) -> Vec<AwardedAchievement> {
+        self.awarded_achievements
+            .get(&account_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl Contract {
+    // 在 mint 和解谜时检查账户新达成的成就
+    pub(crate) fn internal_award_achievements(
+        &mut self,
+        account_id: &AccountId,
+        trigger: AchievementTrigger,
+    ) {
+        let stats = self.view_player_stats(account_id.clone());
+        let mut awarded = self.view_achievements(account_id.clone());
+        let now = env::block_timestamp();
+        let block_height = env::block_height();
+
+        let mut new_ids = vec![];
+        for definition in self.achievements.iter() {
+            if awarded
+                .iter()
+                .any(|achievement| achievement.id == definition.id)
+            {
+                continue;
+            }
+            let achieved = match (&definition.condition, &trigger) {
+                (AchievementCondition::Solves { count }, _) => stats.solves >= *count,
+                (AchievementCondition::MazesCreated { count }, _) => stats.mazes_created >= *count,
+                (
+                    AchievementCondition::SolvedWithinBlocks { blocks },
+                    AchievementTrigger::Solved(maze),
+                ) => maze.created_block.is_some_and(|created_block| {
+                    block_height.saturating_sub(created_block) <= blocks.0
+                }),
+                (
+                    AchievementCondition::MazeSurvived { days },
+                    AchievementTrigger::MazeSolved(maze),
+                ) => maze.created_at.is_some_and(|created_at| {
+                    now.saturating_sub(created_at) / NANOSECONDS_PER_DAY >= u64::from(*days)
+                }),
+                _ => false,
+            };
+            if achieved {
+                new_ids.push(definition.id);
+            }
+        }
+        if new_ids.is_empty() {
+            return;
+        }
+
+        awarded.extend(new_ids.iter().map(|id| AwardedAchievement {
+            id: id.clone(),
+            block_height: U64(block_height),
+        }));
+        self.awarded_achievements
+            .insert(account_id.clone(), awarded);
+
+        MazesEventLog::new(MazesEventLogVariant::AchievementAwarded(
+            new_ids
+                .into_iter()
+                .map(|achievement_id| AchievementAwardedLog {
+                    account_id: account_id.clone(),
+                    achievement_id,
+                })
+                .collect(),
+        ))
+        .emit();
+    }
+}
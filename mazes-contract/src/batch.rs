@@ -0,0 +1,197 @@
+use std::collections::{HashMap, HashSet};
+
+use near_contract_standards::non_fungible_token::metadata::TokenMetadata;
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId};
+
+use crate::*;
+
+// 单次批量操作的 token 数量上限, 避免超出 gas 限制
+pub const MAX_BATCH_SIZE: usize = 20;
+
+/// `nft_batch_mint` 中的一项, 字段与 `mint` 的参数相同
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MintRequest {
+    pub account_id: AccountId,
+    pub metadata: TokenMetadata,
+    pub secret: String,
+    pub royalty: Option<HashMap<AccountId, u32>>,
+    pub difficulty: Option<u8>,
+}
+
+#[near_bindgen]
+impl Contract {
+    // 合约所有者或 Minter 批量 mint, 按顺序分配 id 并返回
+    // 先检查全部请求, 任意一项无效时不会 mint 任何 NFT
+    pub fn nft_batch_mint(&mut self, requests: Vec<MintRequest>) -> Vec<TokenId> {
+        self.assert_role(Role::Minter);
+        self.assert_not_paused(PauseFeature::Mint);
+        require!(!requests.is_empty(), "Batch cannot be empty.");
+        require!(
+            requests.len() <= MAX_BATCH_SIZE,
+            format!("Batch size cannot exceed {}.", MAX_BATCH_SIZE)
+        );
+        // 图片 -> 批次中第一个使用该图片的账户, 批次内的重复图片同样按 `duplicate_policy` 处理
+        let mut batch_media: HashMap<&[u8], &AccountId> = HashMap::new();
+        for request in &requests {
+            self.internal_validate_mint(
+                &request.account_id,
+                &request.metadata,
+                request.royalty.as_ref(),
+                request.difficulty.unwrap_or(1),
+            );
+            let media_hash = match &request.metadata.media_hash {
+                Some(media_hash) => media_hash.0.as_slice(),
+                None => continue,
+            };
+            match batch_media.get(media_hash) {
+                Some(creator_id) => match self.duplicate_policy {
+                    DuplicatePolicy::Reject => {
+                        panic!("This image appears more than once in the batch.")
+                    }
+                    DuplicatePolicy::CreatorOnly => require!(
+                        *creator_id == &request.account_id,
+                        "Only the original creator can mint this image again."
+                    ),
+                    DuplicatePolicy::Allow => {}
+                },
+                None => {
+                    batch_media.insert(media_hash, &request.account_id);
+                }
+            }
+        }
+
+        let mut token_ids = vec![];
+        // 按所有者首次出现的顺序汇总 mint 事件
+        let mut mint_logs: Vec<NftMintLog> = vec![];
+        let mut created_logs = vec![];
+        for request in requests {
+            let token_id = self.next_id().to_string();
+            self.internal_write_maze(
+                &request.account_id,
+                &token_id,
+                &request.metadata,
+                MazeSecret::Plain(request.secret),
+                request.royalty,
+                request.difficulty.unwrap_or(1),
+            );
+
+            match mint_logs
+                .iter_mut()
+                .find(|log| log.owner_id == request.account_id)
+            {
+                Some(log) => log.token_ids.push(token_id.clone()),
+                None => mint_logs.push(NftMintLog {
+                    owner_id: request.account_id.clone(),
+                    token_ids: vec![token_id.clone()],
+                    memo: None,
+                }),
+            }
+            created_logs.push(MazeCreatedLog {
+                creator_id: request.account_id,
+                token_id: token_id.clone(),
+                memo: None,
+            });
+            token_ids.push(token_id);
+        }
+
+        EventLog::nft(EventLogVariant::NftMint(mint_logs)).emit();
+        MazesEventLog::new(MazesEventLogVariant::MazeCreated(created_logs)).emit();
+        token_ids
+    }
+
+    // 持有者直接销毁自己的多个 NFT, 返回空列表
+    // 合约所有者或 Moderator 销毁他人的 NFT 时按时间锁排队, 返回管理操作 id
+    // 任意一个 NFT 无效时全部失败
+    #[payable]
+    pub fn batch_burn(&mut self, token_ids: Vec<TokenId>, memo: Option<String>) -> Vec<u64> {
+        assert_one_yocto();
+        self.assert_not_paused(PauseFeature::Guess);
+        assert_valid_batch(&token_ids);
+        let account_id = env::predecessor_account_id();
+
+        let held = token_ids
+            .iter()
+            .all(|token_id| self.tokens.owner_by_id.get(token_id).as_ref() == Some(&account_id));
+        if !held {
+            self.assert_role(Role::Moderator);
+            let actions: Vec<AdminAction> = token_ids
+                .into_iter()
+                .map(|token_id| {
+                    self.internal_push_admin_action(token_id, AdminActionKind::Burn, memo.clone())
+                })
+                .collect();
+            let action_ids = actions.iter().map(|action| action.id).collect();
+            MazesEventLog::new(MazesEventLogVariant::AdminActionQueued(actions)).emit();
+            return action_ids;
+        }
+
+        // 与 `unwrap_maze` 相同, 托管外部 NFT 的迷宫过期前不能销毁
+        for token_id in &token_ids {
+            if let Some(wrapped) = self.wrapped_nfts.get(token_id) {
+                require!(
+                    env::block_timestamp() >= wrapped.expires_at.0,
+                    format!("Wrapped maze {} has not expired yet.", token_id)
+                );
+            }
+        }
+        for token_id in &token_ids {
+            self.internal_remove_token(token_id);
+            self.internal_refund_escrow(token_id);
+        }
+        EventLog::nft(EventLogVariant::NftBurn(vec![NftBurnLog {
+            authorized_id: None,
+            owner_id: account_id.to_string(),
+            token_ids,
+            memo,
+        }]))
+        .emit();
+        vec![]
+    }
+
+    // 持有者把自己的多个 NFT 转给同一个账户, 任意一个 NFT 无效时全部失败
+    #[payable]
+    pub fn batch_transfer(&mut self, receiver_id: AccountId, token_ids: Vec<TokenId>) {
+        assert_one_yocto();
+        self.assert_not_paused(PauseFeature::Transfer);
+        assert_valid_batch(&token_ids);
+        let account_id = env::predecessor_account_id();
+        require!(
+            receiver_id != account_id,
+            "Current and next owner must differ."
+        );
+        for token_id in &token_ids {
+            require!(
+                self.tokens.owner_by_id.get(token_id).as_ref() == Some(&account_id),
+                format!("Sender does not own token {}.", token_id)
+            );
+        }
+
+        for token_id in &token_ids {
+            self.internal_move_token(token_id, &account_id, &receiver_id);
+        }
+        EventLog::nft(EventLogVariant::NftTransfer(vec![NftTransferLog {
+            authorized_id: None,
+            old_owner_id: account_id.to_string(),
+            new_owner_id: receiver_id.to_string(),
+            token_ids,
+            memo: None,
+        }]))
+        .emit();
+    }
+}
+
+fn assert_valid_batch(token_ids: &[TokenId]) {
+    require!(!token_ids.is_empty(), "Batch cannot be empty.");
+    require!(
+        token_ids.len() <= MAX_BATCH_SIZE,
+        format!("Batch size cannot exceed {}.", MAX_BATCH_SIZE)
+    );
+    let unique: HashSet<&TokenId> = token_ids.iter().collect();
+    require!(
+        unique.len() == token_ids.len(),
+        "Batch contains duplicate token ids."
+    );
+}
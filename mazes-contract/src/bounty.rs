@@ -0,0 +1,283 @@
+use std::collections::{BTreeMap, HashMap};
+
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{
+    env, near_bindgen, require, serde_json, AccountId, Gas, Promise, PromiseOrValue, PromiseResult,
+};
+
+use crate::*;
+
+// 单个迷宫的赏金记录上限, 每条记录在退款时都需要一次跨合约调用
+// 8 次转账 (15 TGas) 加上托管 NFT 的转移 (30 TGas) 共 150 TGas, 前端调用 `user_burn` 时附加 300 TGas
+pub const MAX_BOUNTY_CONTRIBUTIONS: usize = 8;
+// 调用代币合约 `ft_transfer` 的 gas
+const GAS_FOR_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+// `on_bounty_transfer` 回调的 gas
+const GAS_FOR_BOUNTY_CALLBACK: Gas = Gas(5_000_000_000_000);
+
+/// 通过 `ft_transfer_call` 添加赏金时 `msg` 的内容
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BountyMsg {
+    pub token_id: TokenId,
+}
+
+/// 某个账户以某种代币为迷宫添加的赏金
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BountyContribution {
+    pub account_id: AccountId,
+    pub ft_contract_id: AccountId,
+    pub amount: U128,
+}
+
+// 白名单中的 NEP-141 代币合约通过 `ft_transfer_call` 为迷宫添加赏金
+// 出错时 panic, 代币合约会把代币全部退还给发送者
+#[near_bindgen]
+impl FungibleTokenReceiver for Contract {
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        let ft_contract_id = env::predecessor_account_id();
+        let min_amount = self
+            .bounty_tokens
+            .get(&ft_contract_id)
+            .expect("Token is not whitelisted for bounties.");
+        require!(amount.0 > 0, "Bounty amount must be positive.");
+        // 避免用少量代币占满赏金记录
+        require!(
+            amount.0 >= min_amount,
+            format!("Bounty amount cannot be less than {}.", min_amount)
+        );
+        let msg: BountyMsg = serde_json::from_str(&msg).expect("Invalid bounty message.");
+        let token_id = msg.token_id;
+        require!(
+            self.tokens.owner_by_id.contains_key(&token_id),
+            "Token not found."
+        );
+        self.assert_not_expired(&token_id);
+
+        let mut contributions = self.bounties.get(&token_id).cloned().unwrap_or_default();
+        match contributions.iter_mut().find(|contribution| {
+            contribution.account_id == sender_id && contribution.ft_contract_id == ft_contract_id
+        }) {
+            Some(contribution) => {
+                contribution.amount.0 = contribution
+                    .amount
+                    .0
+                    .checked_add(amount.0)
+                    .expect("Bounty overflow.");
+            }
+            None => {
+                require!(
+                    contributions.len() < MAX_BOUNTY_CONTRIBUTIONS,
+                    format!(
+                        "Cannot have more than {} bounty contributions.",
+                        MAX_BOUNTY_CONTRIBUTIONS
+                    )
+                );
+                contributions.push(BountyContribution {
+                    account_id: sender_id.clone(),
+                    ft_contract_id: ft_contract_id.clone(),
+                    amount,
+                });
+            }
+        }
+        self.internal_unindex_bounty(&token_id);
+        self.bounties.insert(token_id.clone(), contributions);
+        self.internal_index_bounty(&token_id);
+        self.internal_update_active_bounty(&ft_contract_id, amount.0, true);
+
+        MazesEventLog::new(MazesEventLogVariant::BountyAdded(vec![BountyAddedLog {
+            account_id: sender_id,
+            token_id,
+            currency: ft_contract_id.to_string(),
+            amount,
+        }]))
+        .emit();
+        PromiseOrValue::Value(U128(0))
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    // 合约所有者或 Treasurer 把代币合约加入赏金白名单, 或修改单次添加赏金的最小数量
+    pub fn add_bounty_token(&mut self, ft_contract_id: AccountId, min_amount: U128) {
+        self.assert_role(Role::Treasurer);
+        self.bounty_tokens.insert(&ft_contract_id, &min_amount.0);
+    }
+
+    // 移出白名单后不能再添加该代币的赏金, 已有的赏金不受影响
+    pub fn remove_bounty_token(&mut self, ft_contract_id: AccountId) {
+        self.assert_role(Role::Treasurer);
+        self.bounty_tokens.remove(&ft_contract_id);
+    }
+
+    // 代币合约 -> 单次添加赏金的最小数量
+    pub fn view_bounty_tokens(&self) -> HashMap<AccountId, U128> {
+        self.bounty_tokens
+            .iter()
+            .map(|(ft_contract_id, min_amount)| (ft_contract_id, U128(min_amount)))
+            .collect()
+    }
+
+    // 查询迷宫的赏金, 按代币合约汇总
+    pub fn view_bounty(&self, token_id: TokenId) -> HashMap<AccountId, U128> {
+        self.internal_bounty_totals(&token_id)
+            .into_iter()
+            .map(|(ft_contract_id, amount)| (ft_contract_id, U128(amount)))
+            .collect()
+    }
+
+    pub fn view_bounty_contributions(&self, token_id: TokenId) -> Vec<BountyContribution> {
+        self.bounties.get(&token_id).cloned().unwrap_or_default()
+    }
+
+    // 查询转账失败, 等待账户领取的赏金
+    pub fn view_bounty_claims(&self, account_id: AccountId) -> HashMap<AccountId, U128> {
+        self.bounty_claims
+            .get(&account_id)
+            .map(|claims| {
+                claims
+                    .iter()
+                    .map(|(ft_contract_id, &amount)| (ft_contract_id.clone(), U128(amount)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // 领取转账失败的赏金, 例如未在代币合约注册存储时
+    pub fn claim_bounty(&mut self, ft_contract_id: AccountId) -> Promise {
+        let account_id = env::predecessor_account_id();
+        let mut claims = self
+            .bounty_claims
+            .remove(&account_id)
+            .expect("No bounty to claim.");
+        let amount = claims.remove(&ft_contract_id).expect("No bounty to claim.");
+        if !claims.is_empty() {
+            self.bounty_claims.insert(account_id.clone(), claims);
+        }
+        self.internal_ft_transfer(
+            &ft_contract_id,
+            &account_id,
+            amount,
+            "Bounty claim".to_string(),
+        )
+    }
+
+    // `ft_transfer` 的回调, 失败时记为待领取并返回 `false`
+    #[private]
+    pub fn on_bounty_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        ft_contract_id: AccountId,
+        amount: U128,
+    ) -> bool {
+        let success = env::promise_results_count() == 1
+            && matches!(env::promise_result(0), PromiseResult::Successful(_));
+        if !success {
+            let mut claims = self
+                .bounty_claims
+                .get(&receiver_id)
+                .cloned()
+                .unwrap_or_default();
+            *claims.entry(ft_contract_id).or_insert(0) += amount.0;
+            self.bounty_claims.insert(receiver_id, claims);
+        }
+        success
+    }
+}
+
+impl Contract {
+    fn internal_bounty_totals(&self, token_id: &TokenId) -> BTreeMap<AccountId, u128> {
+        let mut totals = BTreeMap::new();
+        for contribution in self.bounties.get(token_id).into_iter().flatten() {
+            *totals
+                .entry(contribution.ft_contract_id.clone())
+                .or_insert(0) += contribution.amount.0;
+        }
+        totals
+    }
+
+    // 把迷宫的全部赏金按代币合约汇总后转给猜中的账户
+    pub(crate) fn internal_pay_bounty(&mut self, token_id: &TokenId, receiver_id: &AccountId) {
+        let totals = self.internal_bounty_totals(token_id);
+        self.bounties.remove(token_id);
+
+        let mut logs = vec![];
+        for (ft_contract_id, amount) in totals {
+            self.internal_update_active_bounty(&ft_contract_id, amount, false);
+            self.internal_ft_transfer(
+                &ft_contract_id,
+                receiver_id,
+                amount,
+                format!("Bounty of maze {}", token_id),
+            );
+            logs.push(BountyPaidLog {
+                receiver_id: receiver_id.clone(),
+                token_id: token_id.clone(),
+                currency: ft_contract_id.to_string(),
+                amount: U128(amount),
+            });
+        }
+        if !logs.is_empty() {
+            MazesEventLog::new(MazesEventLogVariant::BountyPaid(logs)).emit();
+        }
+    }
+
+    // 迷宫过期或被管理员销毁时, 把赏金退还给各个出资账户
+    pub(crate) fn internal_refund_bounty(&mut self, token_id: &TokenId) {
+        let contributions = match self.bounties.remove(token_id) {
+            Some(contributions) => contributions,
+            None => return,
+        };
+
+        let mut logs = vec![];
+        for contribution in contributions {
+            self.internal_update_active_bounty(
+                &contribution.ft_contract_id,
+                contribution.amount.0,
+                false,
+            );
+            self.internal_ft_transfer(
+                &contribution.ft_contract_id,
+                &contribution.account_id,
+                contribution.amount.0,
+                format!("Bounty refund of maze {}", token_id),
+            );
+            logs.push(BountyPaidLog {
+                receiver_id: contribution.account_id,
+                token_id: token_id.clone(),
+                currency: contribution.ft_contract_id.to_string(),
+                amount: contribution.amount,
+            });
+        }
+        MazesEventLog::new(MazesEventLogVariant::BountyRefunded(logs)).emit();
+    }
+
+    fn internal_ft_transfer(
+        &self,
+        ft_contract_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: u128,
+        memo: String,
+    ) -> Promise {
+        ext_ft_core::ext(ft_contract_id.clone())
+            .with_attached_deposit(1)
+            .with_static_gas(GAS_FOR_FT_TRANSFER)
+            .ft_transfer(receiver_id.clone(), U128(amount), Some(memo))
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_BOUNTY_CALLBACK)
+                    .on_bounty_transfer(receiver_id.clone(), ft_contract_id.clone(), U128(amount)),
+            )
+    }
+}
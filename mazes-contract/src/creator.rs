@@ -0,0 +1,84 @@
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::collections::UnorderedSet;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+use crate::*;
+
+// 单次查询的迷宫数量上限
+pub const MAX_MAZES_BY_CREATOR_LIMIT: u64 = 100;
+
+#[near_bindgen]
+impl Contract {
+    pub fn view_creator(&self, token_id: TokenId) -> Option<AccountId> {
+        self.creator_by_id.get(&token_id).cloned()
+    }
+
+    pub fn view_mazes_by_creator_supply(&self, account_id: AccountId) -> U128 {
+        U128(
+            self.tokens_per_creator
+                .get(&account_id)
+                .map_or(0, |token_ids| token_ids.len() as u128),
+        )
+    }
+
+    // 查询账户创建且尚未销毁的迷宫, 不论当前由谁持有
+    pub fn view_mazes_by_creator(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<MazeToken> {
+        let limit = limit.unwrap_or(MAX_MAZES_BY_CREATOR_LIMIT);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        let limit = limit.min(MAX_MAZES_BY_CREATOR_LIMIT);
+        let start = usize::try_from(from_index.map_or(0, |index| index.0)).unwrap_or(usize::MAX);
+
+        self.tokens_per_creator
+            .get(&account_id)
+            .map(|token_ids| {
+                token_ids
+                    .iter()
+                    .skip(start)
+                    .take(limit as usize)
+                    .filter_map(|token_id| self.internal_maze_token(token_id))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Contract {
+    // 记录 token_id -> 创建者及创建者 -> token_ids 映射
+    pub(crate) fn internal_add_token_to_creator(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &TokenId,
+    ) {
+        self.creator_by_id
+            .insert(token_id.clone(), account_id.clone());
+        let token_ids = self
+            .tokens_per_creator
+            .entry(account_id.clone())
+            .or_insert_with(|| {
+                UnorderedSet::new(StorageKey::TokensPerCreatorSet {
+                    account_hash: env::sha256(account_id.as_bytes()),
+                })
+            });
+        token_ids.insert(token_id);
+    }
+
+    // 移除 token_id -> 创建者映射, 并从创建者的集合中移除
+    pub(crate) fn internal_remove_token_from_creator(&mut self, token_id: &TokenId) {
+        let account_id = match self.creator_by_id.remove(token_id) {
+            Some(account_id) => account_id,
+            None => return,
+        };
+        if let Some(token_ids) = self.tokens_per_creator.get_mut(&account_id) {
+            token_ids.remove(token_id);
+            if token_ids.is_empty() {
+                self.tokens_per_creator.remove(&account_id);
+            }
+        }
+    }
+}
@@ -1,8 +1,20 @@
 use std::fmt;
 
+use near_sdk::json_types::U128;
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::serde_json;
-use near_sdk::AccountId;
+use near_sdk::{env, AccountId};
+
+use crate::{AdminAction, PauseFeature, Role, Trophy};
+
+/// NEP-171 事件标准
+pub const NFT_STANDARD_NAME: &str = "nep171";
+/// `contract_metadata_update` 在 1.1.0 中引入
+pub const NFT_STANDARD_VERSION: &str = "1.1.0";
+
+/// 迷宫玩法相关的自定义事件标准
+pub const MAZES_STANDARD_NAME: &str = "mazes";
+pub const MAZES_STANDARD_VERSION: &str = "1.0.0";
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "event", content = "data")]
@@ -11,7 +23,9 @@ use near_sdk::AccountId;
 #[non_exhaustive]
 pub enum EventLogVariant {
     NftMint(Vec<NftMintLog>),
-    NftBurnLog(Vec<NftBurnLog>),
+    NftBurn(Vec<NftBurnLog>),
+    NftTransfer(Vec<NftTransferLog>),
+    ContractMetadataUpdate(Vec<ContractMetadataUpdateLog>),
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -25,6 +39,21 @@ pub struct EventLog {
     pub event: EventLogVariant,
 }
 
+impl EventLog {
+    /// 按 NEP-171 标准构造事件
+    pub fn nft(event: EventLogVariant) -> Self {
+        Self {
+            standard: NFT_STANDARD_NAME.to_string(),
+            version: NFT_STANDARD_VERSION.to_string(),
+            event,
+        }
+    }
+
+    pub fn emit(&self) {
+        env::log_str(&self.to_string());
+    }
+}
+
 impl fmt::Display for EventLog {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_fmt(format_args!(
@@ -64,3 +93,243 @@ pub struct NftBurnLog {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memo: Option<String>,
 }
+
+/// Arguments
+/// * `authorized_id`: approved account to transfer
+/// * `old_owner_id`: "owner.near"
+/// * `new_owner_id`: "receiver.near"
+/// * `token_ids`: ["1", "12345abc"]
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftTransferLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorized_id: Option<String>,
+
+    pub old_owner_id: String,
+    pub new_owner_id: String,
+    pub token_ids: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// Arguments
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractMetadataUpdateLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+#[serde(crate = "near_sdk::serde")]
+#[non_exhaustive]
+pub enum MazesEventLogVariant {
+    MazeCreated(Vec<MazeCreatedLog>),
+    GuessFailed(Vec<GuessFailedLog>),
+    MazeSolved(Vec<MazeSolvedLog>),
+    BountyAdded(Vec<BountyAddedLog>),
+    BountyPaid(Vec<BountyPaidLog>),
+    BountyRefunded(Vec<BountyPaidLog>),
+    HintReleased(Vec<HintReleasedLog>),
+    SecretRotated(Vec<SecretRotatedLog>),
+    OwnerProposed(Vec<OwnerProposedLog>),
+    OwnerTransferred(Vec<OwnerTransferredLog>),
+    RoleGranted(Vec<RoleChangedLog>),
+    RoleRevoked(Vec<RoleChangedLog>),
+    PauseChanged(Vec<PauseChangedLog>),
+    AdminActionQueued(Vec<AdminAction>),
+    AdminActionExecuted(Vec<AdminAction>),
+    AdminActionCancelled(Vec<AdminAction>),
+    NftWrapped(Vec<WrappedNftLog>),
+    NftUnwrapped(Vec<WrappedNftLog>),
+    NftUnwrapFailed(Vec<WrappedNftLog>),
+    TrophyMinted(Vec<Trophy>),
+    AchievementAwarded(Vec<AchievementAwardedLog>),
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MazesEventLog {
+    pub standard: String,
+    pub version: String,
+
+    #[serde(flatten)]
+    pub event: MazesEventLogVariant,
+}
+
+impl MazesEventLog {
+    pub fn new(event: MazesEventLogVariant) -> Self {
+        Self {
+            standard: MAZES_STANDARD_NAME.to_string(),
+            version: MAZES_STANDARD_VERSION.to_string(),
+            event,
+        }
+    }
+
+    pub fn emit(&self) {
+        env::log_str(&self.to_string());
+    }
+}
+
+impl fmt::Display for MazesEventLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!(
+            "EVENT_JSON:{}",
+            &serde_json::to_string(self).map_err(|_| fmt::Error)?
+        ))
+    }
+}
+
+/// Arguments
+/// * `creator_id`: account the maze was minted to
+/// * `token_id`: "1"
+/// * `memo`: optional message
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MazeCreatedLog {
+    pub creator_id: AccountId,
+    pub token_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+/// Arguments
+/// * `account_id`: account that guessed
+/// * `token_id`: "1"
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct GuessFailedLog {
+    pub account_id: AccountId,
+    pub token_id: String,
+}
+
+/// Arguments
+/// * `solver_id`: account that guessed the secret
+/// * `owner_id`: owner of the maze when it was solved
+/// * `token_id`: "1"
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MazeSolvedLog {
+    pub solver_id: AccountId,
+    pub owner_id: AccountId,
+    pub token_id: String,
+}
+
+/// Arguments
+/// * `account_id`: account that funded the bounty
+/// * `token_id`: "1"
+/// * `currency`: "near" or the fungible token contract
+/// * `amount`: amount added, in the currency's smallest unit
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BountyAddedLog {
+    pub account_id: AccountId,
+    pub token_id: String,
+    pub currency: String,
+    pub amount: U128,
+}
+
+/// Arguments
+/// * `receiver_id`: account the bounty was paid or refunded to
+/// * `token_id`: "1"
+/// * `currency`: "near" or the fungible token contract
+/// * `amount`: amount paid, in the currency's smallest unit
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BountyPaidLog {
+    pub receiver_id: AccountId,
+    pub token_id: String,
+    pub currency: String,
+    pub amount: U128,
+}
+
+/// Arguments
+/// * `token_id`: "1"
+/// * `hint`: the released hint
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HintReleasedLog {
+    pub token_id: String,
+    pub hint: String,
+}
+
+/// Arguments
+/// * `account_id`: account that changed the secret
+/// * `token_id`: "1"
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SecretRotatedLog {
+    pub account_id: AccountId,
+    pub token_id: String,
+}
+
+/// Arguments
+/// * `owner_id`: current contract owner
+/// * `new_owner_id`: proposed owner, `None` cancels the proposal
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnerProposedLog {
+    pub owner_id: AccountId,
+    pub new_owner_id: Option<AccountId>,
+}
+
+/// Arguments
+/// * `old_owner_id`: previous contract owner
+/// * `new_owner_id`: account that accepted ownership
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OwnerTransferredLog {
+    pub old_owner_id: AccountId,
+    pub new_owner_id: AccountId,
+}
+
+/// Arguments
+/// * `account_id`: account whose roles changed
+/// * `role`: "minter", "moderator", "treasurer" or "pauser"
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RoleChangedLog {
+    pub account_id: AccountId,
+    pub role: Role,
+}
+
+/// Arguments
+/// * `account_id`: account that changed the flag
+/// * `feature`: "mint", "guess", "transfer" or "approval"
+/// * `paused`: new state of the flag
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseChangedLog {
+    pub account_id: AccountId,
+    pub feature: PauseFeature,
+    pub paused: bool,
+}
+
+/// Arguments
+/// * `account_id`: creator for `nft_wrapped`, receiver of the wrapped NFT otherwise
+/// * `token_id`: "1"
+/// * `nft_contract_id`: contract of the wrapped NFT
+/// * `nft_token_id`: id of the wrapped NFT in that contract
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WrappedNftLog {
+    pub account_id: AccountId,
+    pub token_id: String,
+    pub nft_contract_id: AccountId,
+    pub nft_token_id: String,
+}
+
+/// Arguments
+/// * `account_id`: account that earned the achievement
+/// * `achievement_id`: "first_solve"
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AchievementAwardedLog {
+    pub account_id: AccountId,
+    pub achievement_id: String,
+}
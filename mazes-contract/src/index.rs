@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::ops::Bound;
+
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::store::TreeMap;
+use near_sdk::{near_bindgen, require, AccountId};
+
+use crate::*;
+
+/// `view_mazes` 的排序字段
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum MazeSortBy {
+    // 按 mint 顺序, 方向由 `order` 决定
+    #[default]
+    Age,
+    // 按 `bounty_token` 的赏金总额从高到低, 忽略 `order`
+    Bounty,
+}
+
+// 最多保留的已解开迷宫记录数量, 超出时移除 token id 最小 (最早 mint) 的记录
+// 移除后 `view_mazes` 不再列出该迷宫, `view_maze` 仍然返回已解开的状态和难度
+pub const MAX_SOLVED_MAZES: u32 = 1000;
+
+// 解开时记录的迷宫信息, 只保留列表展示需要的字段
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct SolvedMaze {
+    // 解开前的所有者
+    pub owner_id: AccountId,
+    pub solver_id: AccountId,
+    pub creator_id: Option<AccountId>,
+    pub title: Option<String>,
+    pub attempts: u32,
+    // 代币合约 -> 赏金总额
+    pub bounty: HashMap<AccountId, U128>,
+}
+
+impl SolvedMaze {
+    pub(crate) fn summary(&self, id: u64) -> MazeSummary {
+        MazeSummary {
+            token_id: id.to_string(),
+            owner_id: self.owner_id.clone(),
+            creator_id: self.creator_id.clone(),
+            title: self.title.clone(),
+            media_hash: None,
+            bounty: self.bounty.clone(),
+            status: MazeStatus::Solved,
+            attempts: self.attempts,
+            expires_at: None,
+        }
+    }
+}
+
+/// `view_mazes` 的过滤条件, 不传的字段不参与过滤
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MazeFilter {
+    // 不传时只返回 open 的迷宫
+    pub status: Option<MazeStatus>,
+    pub difficulty: Option<u8>,
+    // 赏金使用的代币合约, 按赏金排序或设置 `min_bounty` 时必传
+    pub bounty_token: Option<AccountId>,
+    pub min_bounty: Option<U128>,
+}
+
+#[near_bindgen]
+impl Contract {
+    // 通过二级索引按状态, 难度和赏金查询迷宫
+    // 过期由时间决定, 查询时逐个检查, 单页最多检查 `MAX_PAGE_SCAN` 个迷宫
+    // 已解开的迷宫只列出最近保留的 `MAX_SOLVED_MAZES` 条记录
+    pub fn view_mazes(
+        &self,
+        filter: Option<MazeFilter>,
+        sort_by: Option<MazeSortBy>,
+        order: Option<SortOrder>,
+        limit: Option<u64>,
+        cursor: Option<String>,
+    ) -> Page<MazeSummary> {
+        let filter = filter.unwrap_or_default();
+        let status = filter.status.unwrap_or(MazeStatus::Open);
+        if let Some(difficulty) = filter.difficulty {
+            require!(
+                (1..=MAX_DIFFICULTY).contains(&difficulty),
+                format!("Difficulty must be between 1 and {}.", MAX_DIFFICULTY)
+            );
+        }
+        require!(
+            filter.min_bounty.is_none() || filter.bounty_token.is_some(),
+            "Filtering by bounty requires bounty_token."
+        );
+        let limit = limit.unwrap_or(MAX_PAGE_LIMIT);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        let limit = limit.min(MAX_PAGE_LIMIT) as usize;
+        let order = order.unwrap_or_default();
+        let cursor = cursor.as_deref();
+        let sort_by = sort_by.unwrap_or_default();
+        require!(
+            sort_by == MazeSortBy::Age || status != MazeStatus::Solved,
+            "Solved mazes can only be sorted by age."
+        );
+
+        match (sort_by, status) {
+            (MazeSortBy::Age, MazeStatus::Solved) => {
+                let candidates =
+                    index_ids_by_age(&self.solved_mazes, filter.difficulty, order, cursor).map(
+                        |key| {
+                            let summary = self
+                                .solved_mazes
+                                .get(&key)
+                                .map(|solved| solved.summary(key.1))
+                                .filter(|summary| bounty_matches(&filter, summary));
+                            (key.1.to_string(), summary)
+                        },
+                    );
+                collect_page(candidates, limit)
+            }
+            (MazeSortBy::Age, status) => {
+                let candidates =
+                    index_ids_by_age(&self.maze_index, filter.difficulty, order, cursor).map(
+                        |(_, id)| {
+                            let summary =
+                                self.internal_maze_summary(id.to_string())
+                                    .filter(|summary| {
+                                        summary.status == status && bounty_matches(&filter, summary)
+                                    });
+                            (id.to_string(), summary)
+                        },
+                    );
+                collect_page(candidates, limit)
+            }
+            (MazeSortBy::Bounty, status) => {
+                let bounty_token = filter
+                    .bounty_token
+                    .clone()
+                    .expect("Sorting by bounty requires bounty_token.");
+                let lower = (
+                    bounty_token.clone(),
+                    filter.min_bounty.map_or(0, |amount| amount.0),
+                    0,
+                );
+                let upper = match cursor {
+                    Some(cursor) => {
+                        let (amount, id) = decode_bounty_cursor(cursor);
+                        Bound::Excluded((bounty_token, amount, id))
+                    }
+                    None => Bound::Included((bounty_token, u128::MAX, u64::MAX)),
+                };
+                let in_range = match &upper {
+                    Bound::Included(upper) | Bound::Excluded(upper) => upper > &lower,
+                    Bound::Unbounded => true,
+                };
+                let keys: Box<dyn Iterator<Item = &(AccountId, u128, u64)>> = if in_range {
+                    Box::new(
+                        self.bounty_index
+                            .range((Bound::Included(lower), upper))
+                            .rev()
+                            .map(|(key, _)| key),
+                    )
+                } else {
+                    Box::new(std::iter::empty())
+                };
+                let candidates = keys.map(|(_, amount, id)| {
+                    let summary = self
+                        .internal_maze_summary(id.to_string())
+                        .filter(|summary| {
+                            summary.status == status
+                                && filter.difficulty.is_none_or(|difficulty| {
+                                    self.internal_maze_difficulty(&summary.token_id) == difficulty
+                                })
+                        });
+                    (format!("{}:{}", amount, id), summary)
+                });
+                collect_page(candidates, limit)
+            }
+        }
+    }
+
+    /// 按 token id 顺序 (`from_index` 为 0 时从 "1" 开始) 把升级前 mint 的迷宫
+    /// 补录到难度, 赏金和所有者索引中, 返回补录的迷宫数量, 重复补录没有影响
+    pub fn reindex_mazes(&mut self, from_index: Option<U128>, limit: Option<u64>) -> u64 {
+        self.assert_role(Role::Moderator);
+
+        let mut reindexed = 0;
+        for id in self.invariant_range(from_index, limit) {
+            let token_id = id.to_string();
+            if !self.tokens.owner_by_id.contains_key(&token_id) {
+                continue;
+            }
+            let difficulty = self.internal_maze_difficulty(&token_id);
+            self.internal_index_maze(&token_id, difficulty);
+            self.internal_index_bounty(&token_id);
+            self.internal_sync_owner_index(&token_id, None);
+            reindexed += 1;
+        }
+        reindexed
+    }
+}
+
+impl Contract {
+    // mint 时加入难度索引
+    pub(crate) fn internal_index_maze(&mut self, token_id: &TokenId, difficulty: u8) {
+        if let Ok(id) = token_id.parse() {
+            self.maze_index.insert((difficulty, id), ());
+        }
+    }
+
+    // 销毁时从难度和赏金索引中移除, 需要在移除游戏状态和赏金之前调用
+    pub(crate) fn internal_unindex_maze(&mut self, token_id: &TokenId) {
+        self.internal_unindex_maze_difficulty(token_id);
+        self.internal_unindex_bounty(token_id);
+    }
+
+    // 从难度索引中移除, 需要在修改游戏状态之前调用
+    pub(crate) fn internal_unindex_maze_difficulty(&mut self, token_id: &TokenId) {
+        if let Ok(id) = token_id.parse() {
+            let difficulty = self.internal_maze_difficulty(token_id);
+            self.maze_index.remove(&(difficulty, id));
+        }
+    }
+
+    // 按当前赏金总额加入赏金索引
+    pub(crate) fn internal_index_bounty(&mut self, token_id: &TokenId) {
+        if let Ok(id) = token_id.parse::<u64>() {
+            for (ft_contract_id, amount) in self.view_bounty(token_id.clone()) {
+                self.bounty_index.insert((ft_contract_id, amount.0, id), ());
+            }
+        }
+    }
+
+    // 赏金变化前移除旧的赏金总额
+    pub(crate) fn internal_unindex_bounty(&mut self, token_id: &TokenId) {
+        if let Ok(id) = token_id.parse::<u64>() {
+            for (ft_contract_id, amount) in self.view_bounty(token_id.clone()) {
+                self.bounty_index.remove(&(ft_contract_id, amount.0, id));
+            }
+        }
+    }
+
+    // 解开时记录迷宫信息, 需要在销毁 NFT 之前调用
+    pub(crate) fn internal_record_solved(&mut self, token_id: &TokenId, solver_id: &AccountId) {
+        let id = match token_id.parse() {
+            Ok(id) => id,
+            Err(_) => return,
+        };
+        let summary = match self.internal_maze_summary(token_id.clone()) {
+            Some(summary) => summary,
+            None => return,
+        };
+        let difficulty = self.internal_maze_difficulty(token_id);
+        self.solved_ids.insert(
+            token_id.clone(),
+            (difficulty, self.internal_solve_mode(token_id)),
+        );
+        self.solved_mazes.insert(
+            (difficulty, id),
+            SolvedMaze {
+                owner_id: summary.owner_id,
+                solver_id: solver_id.clone(),
+                creator_id: summary.creator_id,
+                title: summary.title,
+                attempts: summary.attempts,
+                bounty: summary.bounty,
+            },
+        );
+
+        if self.solved_mazes.len() > MAX_SOLVED_MAZES {
+            // 刚解开的迷宫 id 可能最小, 不参与移除
+            let oldest = (1..=MAX_DIFFICULTY)
+                .filter_map(|tier| {
+                    self.solved_mazes
+                        .range((tier, 0)..=(tier, u64::MAX))
+                        .map(|(key, _)| *key)
+                        .find(|&key| key != (difficulty, id))
+                })
+                .min_by_key(|&(_, id)| id);
+            if let Some(oldest) = oldest {
+                self.solved_mazes.remove(&oldest);
+            }
+        }
+    }
+
+    // 解开时记录的难度和猜中方式, 以及尚未被移除的迷宫信息
+    pub(crate) fn internal_solved_maze(
+        &self,
+        token_id: &TokenId,
+    ) -> Option<(u8, SolveMode, Option<&SolvedMaze>)> {
+        let (difficulty, solve_mode) = *self.solved_ids.get(token_id)?;
+        let solved = token_id
+            .parse()
+            .ok()
+            .and_then(|id| self.solved_mazes.get(&(difficulty, id)));
+        Some((difficulty, solve_mode, solved))
+    }
+
+    fn internal_maze_difficulty(&self, token_id: &TokenId) -> u8 {
+        self.mazes
+            .get(token_id)
+            .map_or(MazeState::default().difficulty, |state| state.difficulty)
+    }
+}
+
+// 某个难度的 (难度, token id) 索引
+type IndexKeys<'a> = Box<dyn Iterator<Item = (u8, u64)> + 'a>;
+
+// 按 mint 顺序合并各难度的索引, `difficulty` 为 `None` 时合并全部难度
+fn index_ids_by_age<'a, V>(
+    index: &'a TreeMap<(u8, u64), V>,
+    difficulty: Option<u8>,
+    order: SortOrder,
+    cursor: Option<&str>,
+) -> impl Iterator<Item = (u8, u64)> + 'a
+where
+    V: BorshSerialize + BorshDeserialize,
+{
+    let cursor: Option<u64> = cursor.map(decode_cursor);
+    let tiers = match difficulty {
+        Some(difficulty) => difficulty..=difficulty,
+        None => 1..=MAX_DIFFICULTY,
+    };
+    let mut tiers: Vec<Peekable<IndexKeys<'a>>> = tiers
+        .map(|tier| {
+            let ids: IndexKeys<'a> = match order {
+                SortOrder::OldestFirst => Box::new(
+                    index
+                        .range((
+                            cursor.map_or(Bound::Included((tier, 0)), |id| {
+                                Bound::Excluded((tier, id))
+                            }),
+                            Bound::Included((tier, u64::MAX)),
+                        ))
+                        .map(|(key, _)| *key),
+                ),
+                SortOrder::NewestFirst => Box::new(
+                    index
+                        .range((
+                            Bound::Included((tier, 0)),
+                            cursor.map_or(Bound::Included((tier, u64::MAX)), |id| {
+                                Bound::Excluded((tier, id))
+                            }),
+                        ))
+                        .rev()
+                        .map(|(key, _)| *key),
+                ),
+            };
+            ids.peekable()
+        })
+        .collect();
+
+    std::iter::from_fn(move || {
+        let heads = tiers
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(tier, keys)| keys.peek().map(|&(_, id)| (tier, id)));
+        let (tier, _) = match order {
+            SortOrder::OldestFirst => heads.min_by_key(|&(_, id)| id)?,
+            SortOrder::NewestFirst => heads.max_by_key(|&(_, id)| id)?,
+        };
+        tiers[tier].next()
+    })
+}
+
+// 按顺序取满一页, 多检查一个候选以确定是否还有下一页
+// `candidates` 为 (游标位置, 符合条件的迷宫)
+fn collect_page(
+    candidates: impl Iterator<Item = (String, Option<MazeSummary>)>,
+    limit: usize,
+) -> Page<MazeSummary> {
+    let mut items = vec![];
+    let mut last_position = None;
+    let mut has_more = false;
+    for (scanned, (position, summary)) in candidates.enumerate() {
+        if items.len() == limit || scanned as u64 >= MAX_PAGE_SCAN {
+            has_more = true;
+            break;
+        }
+        items.extend(summary);
+        last_position = Some(position);
+    }
+    Page {
+        items,
+        next_cursor: last_position.filter(|_| has_more).map(encode_cursor),
+    }
+}
+
+fn bounty_matches(filter: &MazeFilter, summary: &MazeSummary) -> bool {
+    match (&filter.bounty_token, filter.min_bounty) {
+        (Some(bounty_token), Some(min_bounty)) => summary
+            .bounty
+            .get(bounty_token)
+            .is_some_and(|amount| amount.0 >= min_bounty.0),
+        _ => true,
+    }
+}
+
+fn decode_bounty_cursor(cursor: &str) -> (u128, u64) {
+    let position: String = decode_cursor(cursor);
+    position
+        .split_once(':')
+        .and_then(|(amount, id)| Some((amount.parse().ok()?, id.parse().ok()?)))
+        .expect("Invalid cursor.")
+}
@@ -0,0 +1,197 @@
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{near_bindgen, require, AccountId};
+
+use crate::*;
+
+// 单次检查的 token 数量上限, 避免超出 gas 限制
+pub const MAX_INVARIANT_CHECK_LIMIT: u64 = 100;
+// 单次检查的账户数量上限, 每个账户的集合最多检查 `limit` 个条目
+pub const MAX_INVARIANT_CHECK_ACCOUNTS: usize = 10;
+
+/// 索引之间不一致的情况
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InvariantViolation {
+    // token 有所有者, 但不在所有者的 tokens_per_owner 中
+    MissingFromOwnerSet {
+        token_id: TokenId,
+        owner_id: AccountId,
+    },
+    // token 出现在某账户的 tokens_per_owner 中, 但该账户并不是所有者
+    StaleOwnerSetEntry {
+        token_id: TokenId,
+        account_id: AccountId,
+    },
+    // token 有所有者, 但没有 metadata
+    MissingMetadata {
+        token_id: TokenId,
+    },
+    // token 有所有者, 但没有 secret
+    MissingSecret {
+        token_id: TokenId,
+    },
+    // token 已不存在, 但 metadata 仍然保留
+    OrphanedMetadata {
+        token_id: TokenId,
+    },
+    // token 已不存在, 但 secret 仍然保留
+    OrphanedSecret {
+        token_id: TokenId,
+    },
+}
+
+#[near_bindgen]
+impl Contract {
+    /// 按 token id 顺序 (`from_index` 为 0 时从 "1" 开始) 交叉检查
+    /// `owner_by_id`, `tokens_per_owner`, `token_metadata_by_id` 和 `secret`
+    ///
+    /// `tokens_per_owner` 无法遍历, 过期条目只在 `account_ids` 的集合中查找,
+    /// 每个集合检查从第 `from_index` 个开始的 `limit` 个条目
+    pub fn check_invariants(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        account_ids: Option<Vec<AccountId>>,
+    ) -> Vec<InvariantViolation> {
+        let mut violations = vec![];
+
+        for id in self.invariant_range(from_index, limit) {
+            let token_id = id.to_string();
+            // 未启用 metadata 扩展时为 `None`
+            let has_metadata = self
+                .tokens
+                .token_metadata_by_id
+                .as_ref()
+                .map(|by_id| by_id.contains_key(&token_id));
+            let has_secret = self.secret.contains_key(&token_id)
+                || self.secret_commitments.contains_key(&token_id);
+
+            match self.tokens.owner_by_id.get(&token_id) {
+                Some(owner_id) => {
+                    // 未启用 enumeration 扩展时视为一致
+                    let in_owner_set = self.tokens.tokens_per_owner.as_ref().map(|per_owner| {
+                        per_owner
+                            .get(&owner_id)
+                            .is_some_and(|token_ids| token_ids.contains(&token_id))
+                    });
+                    if in_owner_set == Some(false) {
+                        violations.push(InvariantViolation::MissingFromOwnerSet {
+                            token_id: token_id.clone(),
+                            owner_id: owner_id.clone(),
+                        });
+                    }
+                    if has_metadata == Some(false) {
+                        violations.push(InvariantViolation::MissingMetadata {
+                            token_id: token_id.clone(),
+                        });
+                    }
+                    if !has_secret {
+                        violations.push(InvariantViolation::MissingSecret { token_id });
+                    }
+                }
+                None => {
+                    if has_metadata == Some(true) {
+                        violations.push(InvariantViolation::OrphanedMetadata {
+                            token_id: token_id.clone(),
+                        });
+                    }
+                    if has_secret {
+                        violations.push(InvariantViolation::OrphanedSecret { token_id });
+                    }
+                }
+            }
+        }
+
+        let account_ids = account_ids.unwrap_or_default();
+        require!(
+            account_ids.len() <= MAX_INVARIANT_CHECK_ACCOUNTS,
+            format!(
+                "Cannot check more than {} accounts.",
+                MAX_INVARIANT_CHECK_ACCOUNTS
+            )
+        );
+        if let Some(per_owner) = &self.tokens.tokens_per_owner {
+            let start =
+                usize::try_from(from_index.map_or(0, |index| index.0)).unwrap_or(usize::MAX);
+            let limit = limit.unwrap_or(MAX_INVARIANT_CHECK_LIMIT) as usize;
+            for account_id in account_ids {
+                let token_ids = match per_owner.get(&account_id) {
+                    Some(token_ids) => token_ids,
+                    None => continue,
+                };
+                for token_id in token_ids.iter().skip(start).take(limit) {
+                    if self.tokens.owner_by_id.get(&token_id).as_ref() != Some(&account_id) {
+                        violations.push(InvariantViolation::StaleOwnerSetEntry {
+                            token_id,
+                            account_id: account_id.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        violations
+    }
+
+    /// 修复 `check_invariants` 在同一范围内报告的问题, 返回已修复的条目
+    ///
+    /// 丢失的 metadata 和 secret 无法恢复, 不会出现在返回值中
+    pub fn repair_indexes(
+        &mut self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        account_ids: Option<Vec<AccountId>>,
+    ) -> Vec<InvariantViolation> {
+        self.assert_role(Role::Moderator);
+
+        let mut repaired = vec![];
+        for violation in self.check_invariants(from_index, limit, account_ids) {
+            match &violation {
+                InvariantViolation::MissingFromOwnerSet { token_id, owner_id } => {
+                    self.internal_add_token_to_owner(owner_id, token_id);
+                }
+                InvariantViolation::StaleOwnerSetEntry {
+                    token_id,
+                    account_id,
+                } => {
+                    self.internal_remove_token_from_owner(account_id, token_id);
+                }
+                InvariantViolation::OrphanedMetadata { token_id } => {
+                    if let Some(token_metadata_by_id) = &mut self.tokens.token_metadata_by_id {
+                        token_metadata_by_id.remove(token_id);
+                    }
+                }
+                InvariantViolation::OrphanedSecret { token_id } => {
+                    self.secret.remove(token_id);
+                    self.secret_commitments.remove(token_id);
+                }
+                InvariantViolation::MissingMetadata { .. }
+                | InvariantViolation::MissingSecret { .. } => continue,
+            }
+            repaired.push(violation);
+        }
+        repaired
+    }
+
+    pub(crate) fn invariant_range(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> std::ops::RangeInclusive<u64> {
+        let limit = limit.unwrap_or(MAX_INVARIANT_CHECK_LIMIT);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        require!(
+            limit <= MAX_INVARIANT_CHECK_LIMIT,
+            format!("Limit cannot exceed {}.", MAX_INVARIANT_CHECK_LIMIT)
+        );
+
+        let start = u64::try_from(from_index.map_or(0, |index| index.0))
+            .unwrap_or(u64::MAX)
+            .saturating_add(1);
+        let end = start.saturating_add(limit - 1).min(self.unique_id);
+        start..=end
+    }
+}
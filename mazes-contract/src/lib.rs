@@ -1,20 +1,68 @@
-use near_contract_standards::non_fungible_token::metadata::{NFTContractMetadata, TokenMetadata};
-use near_contract_standards::non_fungible_token::{NonFungibleToken, TokenId};
-use near_contract_standards::{
-    impl_non_fungible_token_approval, impl_non_fungible_token_core,
-    impl_non_fungible_token_enumeration,
+use std::collections::{HashMap, HashSet};
+
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
+use near_contract_standards::fungible_token::FungibleToken;
+use near_contract_standards::impl_non_fungible_token_enumeration;
+use near_contract_standards::non_fungible_token::metadata::{
+    NFTContractMetadata, NonFungibleTokenMetadataProvider, TokenMetadata,
 };
+use near_contract_standards::non_fungible_token::{NonFungibleToken, TokenId};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::store::LookupMap;
+use near_sdk::collections::{LazyOption, UnorderedMap, UnorderedSet, Vector};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::store::{LookupMap, TreeMap};
 
-use near_sdk::{near_bindgen, AccountId, BorshStorageKey, PanicOnDefault, Promise, PromiseOrValue};
+use near_sdk::{near_bindgen, AccountId, BorshStorageKey, PanicOnDefault};
 
+pub use crate::access::*;
+pub use crate::achievement::*;
+pub use crate::batch::*;
+pub use crate::bounty::*;
+pub use crate::creator::*;
 pub use crate::events::*;
-pub use crate::mazes::*;
+pub use crate::index::*;
+pub use crate::invariants::*;
+pub use crate::metadata::{default_contract_metadata, NFT_METADATA_SPEC};
+pub use crate::nft_core::MazeToken;
+pub use crate::pagination::*;
+pub use crate::pause::*;
+pub use crate::reward::*;
+pub use crate::royalty::*;
+pub use crate::stats::*;
+pub use crate::summary::*;
+pub use crate::timelock::*;
+pub use crate::trophy::*;
+pub use crate::upgrade::*;
+pub use crate::wrap::*;
 
+pub mod access;
+pub mod achievement;
+pub mod batch;
+pub mod bounty;
+pub mod creator;
 pub mod events;
+pub mod index;
+pub mod invariants;
 pub mod mazes;
+pub mod metadata;
+pub mod nft_core;
+pub mod pagination;
+pub mod pause;
+pub mod reward;
+pub mod royalty;
+pub mod stats;
+pub mod summary;
+pub mod timelock;
+pub mod trophy;
+pub mod upgrade;
+pub mod wrap;
 
+// test.rs 自身包含 `mod test`
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod test;
+
+// 修改字段时需要在 upgrade.rs 中实现已部署布局的迁移
 #[near_bindgen] // 定义合约根结构, 一个项目中只能有一个根结构
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)] // 实现 borsh 序列化, 实现不可用的 `default` 方法以通过编译
 pub struct Contract {
@@ -26,6 +74,143 @@ pub struct Contract {
     unique_id: u64,
     // 该容器内的数据与容器本身分开存储, 容器本身是根结构的一部分, 但内部数据是独立的存储记录
     secret: LookupMap<TokenId, String>,
+    // media_hash -> 最早使用该图片的 NFT 及使用数量, 用于防止重复 mint 同一张图片
+    media_index: LookupMap<Vec<u8>, MediaRecord>,
+    // 重复图片的 mint 策略
+    duplicate_policy: DuplicatePolicy,
+    // token_id -> 版税账户及其万分比
+    royalties: LookupMap<TokenId, HashMap<AccountId, u32>>,
+    // 合约 metadata, 由合约所有者维护
+    metadata: LazyOption<NFTContractMetadata>,
+    // 已提名但尚未接受的新所有者
+    pending_owner_id: Option<AccountId>,
+    // 账户 -> 被授予的角色
+    roles: LookupMap<AccountId, HashSet<Role>>,
+    // 各功能的暂停状态
+    paused: PauseState,
+    // 管理操作需要等待的时间 (纳秒)
+    admin_timelock: u64,
+    // 所有管理操作及其审计记录, 下标即操作 id
+    admin_actions: Vector<AdminAction>,
+    // 迷宫 token_id -> 托管的外部 NFT
+    wrapped_nfts: LookupMap<TokenId, WrappedNft>,
+    // 可以用作赏金的 NEP-141 代币合约 -> 单次添加赏金的最小数量
+    bounty_tokens: UnorderedMap<AccountId, u128>,
+    // 迷宫 token_id -> 各账户添加的赏金
+    bounties: LookupMap<TokenId, Vec<BountyContribution>>,
+    // 账户 -> 转账失败等待领取的赏金 (代币合约 -> 数量)
+    bounty_claims: LookupMap<AccountId, HashMap<AccountId, u128>>,
+    // 迷宫 token_id -> 创建者 (mint 时的所有者)
+    creator_by_id: LookupMap<TokenId, AccountId>,
+    // 创建者 -> 创建且尚未销毁的迷宫
+    tokens_per_creator: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    // 迷宫 token_id -> 游戏状态
+    mazes: LookupMap<TokenId, MazeState>,
+    // 解谜奖励 MAZE
+    ft: FungibleToken,
+    ft_metadata: LazyOption<FungibleTokenMetadata>,
+    reward_config: RewardConfig,
+    // 所有奖杯, 下标加 1 即奖杯编号
+    trophies: Vector<Trophy>,
+    // 账户 -> 奖杯下标
+    trophies_per_owner: LookupMap<AccountId, Vec<u64>>,
+    // 账户 -> 游戏统计
+    player_stats: LookupMap<AccountId, PlayerStats>,
+    // 合约所有者注册的成就
+    achievements: Vector<AchievementDefinition>,
+    // 账户 -> 已获得的成就
+    awarded_achievements: LookupMap<AccountId, Vec<AwardedAchievement>>,
+    // (难度, token id) -> (), 尚未解开的迷宫, 按难度和 mint 顺序排序
+    maze_index: TreeMap<(u8, u64), ()>,
+    // (代币合约, 赏金总额, token id) -> (), 尚未解开的迷宫按赏金排序
+    bounty_index: TreeMap<(AccountId, u128, u64), ()>,
+    // (难度, token id) -> 解开时记录的信息, 最多保留 `MAX_SOLVED_MAZES` 个
+    solved_mazes: TreeMap<(u8, u64), SolvedMaze>,
+    // 全局统计
+    stats: ContractStats,
+    // 迷宫 token_id -> 各账户猜测的次数
+    attempts_by_account: LookupMap<TokenId, HashMap<AccountId, u32>>,
+    // (所有者, token id) -> (), 按 id 顺序分页查询账户持有的 NFT
+    owner_index: TreeMap<(AccountId, u64), ()>,
+    // 迷宫 token_id -> 所有者已公开的提示
+    hints: LookupMap<TokenId, Vec<String>>,
+    // 迷宫 token_id -> secret 的承诺, 托管外部 NFT 的迷宫不保存 secret 明文
+    secret_commitments: LookupMap<TokenId, SecretCommitment>,
+    // 已解开的迷宫 token_id -> (难度, 猜中方式), `solved_mazes` 移除记录后仍然保留
+    solved_ids: LookupMap<TokenId, (u8, SolveMode)>,
+}
+
+// 同一张图片 (相同 media_hash) 被重复 mint 时的处理策略
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicatePolicy {
+    // 拒绝重复 mint
+    Reject,
+    // 允许重复 mint
+    Allow,
+    // 只允许最初的创建者重复 mint
+    CreatorOnly,
+}
+
+// 迷宫难度的上限, 难度从 1 开始
+pub const MAX_DIFFICULTY: u8 = 5;
+// 每个迷宫最多公开的提示数量
+pub const MAX_HINTS: usize = 5;
+// 单条提示的最大长度 (字节)
+pub const MAX_HINT_LENGTH: usize = 256;
+
+// 迷宫的游戏状态
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct MazeState {
+    pub difficulty: u8,
+    // mint 时间 (纳秒), 升级前 mint 的迷宫未知, 为 `None`
+    pub created_at: Option<u64>,
+    // mint 时的区块高度, 升级前 mint 的迷宫未知, 为 `None`
+    pub created_block: Option<u64>,
+    // 被猜测的次数
+    pub attempts: u32,
+    // 难度由合约所有者或 Minter 设置, 只有这样的迷宫解开时才发放 MAZE
+    pub rated: bool,
+    // 已向创建者发放存活奖励的天数
+    pub creator_reward_days: u64,
+}
+
+// 升级前 mint 的迷宫没有记录状态, 视为难度 1, 不参与按存活时间计算的成就和奖励
+impl Default for MazeState {
+    fn default() -> Self {
+        Self {
+            difficulty: 1,
+            created_at: None,
+            created_block: None,
+            attempts: 0,
+            rated: false,
+            creator_reward_days: 0,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct MediaRecord {
+    pub token_id: TokenId,
+    pub creator_id: AccountId,
+    // 使用该图片且尚未销毁的 NFT 数量, 为 0 时移除记录
+    pub copies: u32,
+}
+
+// 迷宫的 secret, 保存明文或承诺
+pub enum MazeSecret {
+    Plain(String),
+    Commitment(SecretCommitment),
+}
+
+// `hash` 为 `salt` 与 secret 拼接后的 sha256
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SecretCommitment {
+    pub hash: Vec<u8>,
+    pub salt: String,
 }
 
 // 存储在链上的键结构
@@ -38,4 +223,34 @@ pub enum StorageKey {
     // 以 1u8 的方式 borsh 序列化
     #[allow(unused)]
     Secret,
+    MediaIndex,
+    Royalties,
+    Metadata,
+    Roles,
+    AdminActions,
+    WrappedNfts,
+    BountyTokens,
+    Bounties,
+    BountyClaims,
+    CreatorById,
+    Mazes,
+    FungibleToken,
+    FtMetadata,
+    Trophies,
+    TrophiesPerOwner,
+    PlayerStats,
+    Achievements,
+    AwardedAchievements,
+    TokensPerCreator,
+    TokensPerCreatorSet {
+        account_hash: Vec<u8>,
+    },
+    MazeIndex,
+    BountyIndex,
+    SolvedMazes,
+    AttemptsByAccount,
+    OwnerIndex,
+    Hints,
+    SecretCommitments,
+    SolvedIds,
 }
@@ -1,18 +1,21 @@
 use near_contract_standards::non_fungible_token::{Token, TokenId};
 use near_sdk::collections::UnorderedSet;
-use near_sdk::json_types::U128;
+use near_sdk::json_types::{Base64VecU8, U128};
 use near_sdk::{env, near_bindgen, require, AccountId};
 
 use crate::*;
 
-pub const NFT_METADATA_SPEC: &str = "1.0.0";
-pub const NFT_STANDARD_NAME: &str = "nep177";
-
 // 定义合约方法
 #[near_bindgen]
 impl Contract {
     #[init] // 标记合约初始化方法
-    pub fn init(owner_id: AccountId) -> Self {
+    pub fn init(owner_id: AccountId, metadata: Option<NFTContractMetadata>) -> Self {
+        let metadata = metadata.unwrap_or_else(default_contract_metadata);
+        metadata::assert_valid_contract_metadata(&metadata);
+        write_state_version(CURRENT_STATE_VERSION);
+        let mut achievements = Vector::new(StorageKey::Achievements);
+        achievements.extend(default_achievements());
+
         Self {
             owner_id: owner_id.clone(),
             tokens: NonFungibleToken::new(
@@ -24,104 +27,299 @@ impl Contract {
             ),
             unique_id: 0,
             secret: LookupMap::new(StorageKey::Secret),
+            media_index: LookupMap::new(StorageKey::MediaIndex),
+            duplicate_policy: DuplicatePolicy::Reject,
+            royalties: LookupMap::new(StorageKey::Royalties),
+            metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
+            pending_owner_id: None,
+            roles: LookupMap::new(StorageKey::Roles),
+            paused: PauseState::default(),
+            admin_timelock: DEFAULT_ADMIN_TIMELOCK,
+            admin_actions: Vector::new(StorageKey::AdminActions),
+            wrapped_nfts: LookupMap::new(StorageKey::WrappedNfts),
+            bounty_tokens: UnorderedMap::new(StorageKey::BountyTokens),
+            bounties: LookupMap::new(StorageKey::Bounties),
+            bounty_claims: LookupMap::new(StorageKey::BountyClaims),
+            creator_by_id: LookupMap::new(StorageKey::CreatorById),
+            tokens_per_creator: LookupMap::new(StorageKey::TokensPerCreator),
+            mazes: LookupMap::new(StorageKey::Mazes),
+            ft: FungibleToken::new(StorageKey::FungibleToken),
+            ft_metadata: LazyOption::new(StorageKey::FtMetadata, Some(&default_ft_metadata())),
+            reward_config: RewardConfig::default(),
+            trophies: Vector::new(StorageKey::Trophies),
+            trophies_per_owner: LookupMap::new(StorageKey::TrophiesPerOwner),
+            player_stats: LookupMap::new(StorageKey::PlayerStats),
+            achievements,
+            awarded_achievements: LookupMap::new(StorageKey::AwardedAchievements),
+            maze_index: TreeMap::new(StorageKey::MazeIndex),
+            bounty_index: TreeMap::new(StorageKey::BountyIndex),
+            solved_mazes: TreeMap::new(StorageKey::SolvedMazes),
+            stats: ContractStats::default(),
+            attempts_by_account: LookupMap::new(StorageKey::AttemptsByAccount),
+            owner_index: TreeMap::new(StorageKey::OwnerIndex),
+            hints: LookupMap::new(StorageKey::Hints),
+            secret_commitments: LookupMap::new(StorageKey::SecretCommitments),
+            solved_ids: LookupMap::new(StorageKey::SolvedIds),
         }
     }
 
-    // set NFT secret
+    // 合约所有者更新合约 metadata
+    pub fn set_contract_metadata(&mut self, metadata: NFTContractMetadata, memo: Option<String>) {
+        self.assert_owner();
+        metadata::assert_valid_contract_metadata(&metadata);
+        self.metadata.set(&metadata);
+
+        EventLog::nft(EventLogVariant::ContractMetadataUpdate(vec![
+            ContractMetadataUpdateLog { memo },
+        ]))
+        .emit();
+    }
+
+    // 所有者修改 NFT 的 secret
     pub fn set_account_description(&mut self, token_id: TokenId, secret: String) {
-        self.secret.insert(token_id, secret);
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .expect("Token not found.");
+        require!(
+            env::predecessor_account_id() == owner_id,
+            "Only token owner can change the secret."
+        );
+        require!(
+            !self.secret_commitments.contains_key(&token_id),
+            "Cannot change the secret of a wrapped maze."
+        );
+        require!(
+            !self.bounties.contains_key(&token_id),
+            "Cannot change the secret of a maze with a bounty."
+        );
+        self.secret.insert(token_id.clone(), secret);
+
+        MazesEventLog::new(MazesEventLogVariant::SecretRotated(vec![
+            SecretRotatedLog {
+                account_id: owner_id,
+                token_id,
+            },
+        ]))
+        .emit();
     }
 
-    // mint NFT
+    // 所有者公开迷宫的一条提示
+    pub fn release_hint(&mut self, token_id: TokenId, hint: String) {
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .expect("Token not found.");
+        require!(
+            env::predecessor_account_id() == owner_id,
+            "Only token owner can release hints."
+        );
+        require!(
+            !hint.is_empty() && hint.len() <= MAX_HINT_LENGTH,
+            format!("Hint must be between 1 and {} bytes.", MAX_HINT_LENGTH)
+        );
+        let mut hints = self.hints.get(&token_id).cloned().unwrap_or_default();
+        require!(
+            hints.len() < MAX_HINTS,
+            format!("Cannot release more than {} hints.", MAX_HINTS)
+        );
+        hints.push(hint.clone());
+        self.hints.insert(token_id.clone(), hints);
+
+        MazesEventLog::new(MazesEventLogVariant::HintReleased(vec![HintReleasedLog {
+            token_id,
+            hint,
+        }]))
+        .emit();
+    }
+
+    // mint NFT, `royalty` 为版税账户及其万分比, 不传时创建者获得默认版税
+    // `difficulty` 为 1 到 `MAX_DIFFICULTY`, 只有合约所有者或 Minter 可以设置, 不传时为 1
     pub fn mint(
         &mut self,
         account_id: AccountId,
         metadata: TokenMetadata,
         secret: String,
         memo: Option<String>,
+        royalty: Option<HashMap<AccountId, u32>>,
+        difficulty: Option<u8>,
     ) {
+        self.assert_not_paused(PauseFeature::Mint);
+        self.assert_can_set_difficulty(difficulty);
         let token_id = self.next_id().to_string();
-        self.internal_mint(&account_id, &token_id, &metadata, memo, secret);
+        self.internal_mint(
+            &account_id,
+            &token_id,
+            &metadata,
+            memo,
+            MazeSecret::Plain(secret),
+            royalty,
+            difficulty.unwrap_or(1),
+        );
     }
 
-    //  burn NFT
-    pub fn burn(&mut self, account_id: AccountId, token_id: TokenId, memo: Option<String>) {
+    // 合约所有者或 Minter 评定迷宫难度, 评定后的迷宫解开时发放 MAZE
+    pub fn rate_maze(&mut self, token_id: TokenId, difficulty: u8) {
+        self.assert_role(Role::Minter);
+        require!(
+            (1..=MAX_DIFFICULTY).contains(&difficulty),
+            format!("Difficulty must be between 1 and {}.", MAX_DIFFICULTY)
+        );
         require!(
-            env::predecessor_account_id() == self.owner_id,
-            "Only contract owner can call this method."
+            self.tokens.owner_by_id.contains_key(&token_id),
+            "Token not found."
         );
-        self.internal_burn(&account_id, &token_id, memo);
+        let mut state = self.mazes.get(&token_id).cloned().unwrap_or_default();
+        self.internal_unindex_maze_difficulty(&token_id);
+        state.difficulty = difficulty;
+        state.rated = true;
+        self.mazes.insert(token_id.clone(), state);
+        self.internal_index_maze(&token_id, difficulty);
     }
 
-    // user burn NFT
-    pub fn user_burn(
-        &mut self,
-        account_id: AccountId,
-        token_id: TokenId,
-        secret: &String,
-        memo: Option<String>,
-    ) {
-        let token_secret = self.secret.get(&token_id);
+    // 猜测 secret, 猜中则销毁 NFT 并返回 `true`, 托管的外部 NFT 及赏金转给猜中的账户
+    // 猜错不会 panic, 以便在链上记录失败的猜测
+    // near_bindgen 无法反序列化 `&str` 参数
+    #[allow(clippy::ptr_arg)]
+    pub fn user_burn(&mut self, token_id: TokenId, secret: &String, memo: Option<String>) -> bool {
+        self.assert_not_paused(PauseFeature::Guess);
+        self.assert_not_expired(&token_id);
+        let account_id = env::predecessor_account_id();
+        let matches = self.internal_secret_matches(&token_id, secret);
+        let mut state = self.mazes.get(&token_id).cloned().unwrap_or_default();
+        state.attempts += 1;
+        self.mazes.insert(token_id.clone(), state.clone());
+        let mut attempts = self
+            .attempts_by_account
+            .get(&token_id)
+            .cloned()
+            .unwrap_or_default();
+        *attempts.entry(account_id.clone()).or_insert(0) += 1;
+        self.attempts_by_account.insert(token_id.clone(), attempts);
 
-        require!(
-            token_secret.expect("The secret is not correct.") == secret,
-            "The secret is not correct."
-        );
-        self.internal_burn(&account_id, &token_id, memo);
+        self.stats.guesses += 1;
+
+        if !matches {
+            self.stats.failed_guesses += 1;
+            self.internal_update_player_stats(&account_id, |stats| {
+                stats.guesses += 1;
+                stats.failed_guesses += 1;
+            });
+            MazesEventLog::new(MazesEventLogVariant::GuessFailed(vec![GuessFailedLog {
+                account_id,
+                token_id,
+            }]))
+            .emit();
+            return false;
+        }
+
+        self.stats.solved += 1;
+        self.internal_reward_solve(&token_id, &account_id);
+        // 创建者或所有者自己解开的迷宫不发放奖杯和赏金, 也不计入成就和解开次数
+        let self_solve = self.internal_is_self_solve(&token_id, &account_id);
+        if self_solve {
+            self.internal_update_player_stats(&account_id, |stats| stats.guesses += 1);
+        } else {
+            self.internal_mint_trophy(&token_id, &account_id);
+            self.internal_update_player_stats(&account_id, |stats| {
+                stats.guesses += 1;
+                stats.solves += 1;
+            });
+            self.internal_award_achievements(&account_id, AchievementTrigger::Solved(&state));
+            if let Some(creator_id) = self.creator_by_id.get(&token_id).cloned() {
+                self.internal_award_achievements(
+                    &creator_id,
+                    AchievementTrigger::MazeSolved(&state),
+                );
+            }
+        }
+        self.internal_record_solved(&token_id, &account_id);
+        let owner_id = self.internal_burn(&token_id, memo);
+        // 迷宫托管的外部 NFT 及赏金都转给猜中的账户, 自己解开时赏金退还给出资账户
+        self.internal_release_wrapped_nft(&token_id, &account_id);
+        if self_solve {
+            self.internal_refund_bounty(&token_id);
+        } else {
+            self.internal_pay_bounty(&token_id, &account_id);
+        }
+        MazesEventLog::new(MazesEventLogVariant::MazeSolved(vec![MazeSolvedLog {
+            solver_id: account_id,
+            owner_id,
+            token_id,
+        }]))
+        .emit();
+        true
     }
 
-    // 转移 NFT
-    pub fn transfer_maze(
-        &mut self,
-        receiver_id: AccountId,
-        token_id: TokenId,
-        approval_id: Option<u64>,
-        memo: Option<String>,
-    ) {
-        require!(
-            env::predecessor_account_id() == self.owner_id,
-            "Only contract owner can call this method."
-        );
-        self.nft_transfer(receiver_id, token_id, approval_id, memo)
+    // 设置重复图片的 mint 策略
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        self.assert_role(Role::Moderator);
+        self.duplicate_policy = policy;
+    }
+
+    pub fn view_duplicate_policy(&self) -> DuplicatePolicy {
+        self.duplicate_policy
+    }
+
+    // 查询最早使用该图片的 NFT, 仍有副本时可能已被销毁, `media_hash` 为 base64 编码的 sha256
+    pub fn view_token_by_media_hash(&self, media_hash: Base64VecU8) -> Option<TokenId> {
+        self.media_index
+            .get(&media_hash.0)
+            .map(|record| record.token_id.clone())
     }
 
-    // 查询某个用户拥有的NFT
+    // 查询某个用户拥有的NFT, 没有 NFT 的账户返回空列表
     pub fn view_nft_by_id(
         &self,
         account_id: AccountId,
         from_index: Option<U128>,
         limit: Option<u64>,
-    ) -> Vec<Token> {
-        let tokens_per_owner = self
-            .tokens
-            .tokens_per_owner
-            .as_ref()
-            .expect("Could not find tokens_per_owner in view_nft_by_id enumeration.");
-        let token_set = tokens_per_owner
-            .get(&account_id)
-            .expect("The account does not own any tokens.");
-        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
-        assert_ne!(limit, 0, "Cannot provide limit of 0.");
-        if limit == 0 {
-            return vec![];
-        }
-        let start_index = from_index.map(From::from).unwrap_or_default();
-
-        if token_set.len() as u128 <= start_index {
-            return vec![];
-        }
-
-        token_set
-            .iter()
-            .skip(start_index as usize)
-            .map(|token_id| self.nft_token(token_id).unwrap())
-            .collect()
+        cursor: Option<String>,
+        order: Option<SortOrder>,
+    ) -> Page<MazeToken> {
+        let page = self.internal_page_token_ids(
+            Some(&account_id),
+            PageRequest {
+                from_index,
+                limit,
+                cursor,
+                order,
+            },
+        );
+        self.internal_page_maze_tokens(page)
     }
 
     // 查看所有的nft
-    pub fn view_all_nft(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Token> {
-        let all_tokens = self.tokens.nft_tokens(from_index, limit);
-        all_tokens.into_iter().collect()
+    pub fn view_all_nft(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        cursor: Option<String>,
+        order: Option<SortOrder>,
+    ) -> Page<MazeToken> {
+        let page = self.internal_page_token_ids(
+            None,
+            PageRequest {
+                from_index,
+                limit,
+                cursor,
+                order,
+            },
+        );
+        self.internal_page_maze_tokens(page)
+    }
+
+    fn internal_page_maze_tokens(&self, page: Page<TokenId>) -> Page<MazeToken> {
+        Page {
+            items: page
+                .items
+                .into_iter()
+                .filter_map(|token_id| self.internal_maze_token(token_id))
+                .collect(),
+            next_cursor: page.next_cursor,
+        }
     }
 
     pub(crate) fn next_id(&mut self) -> u64 {
@@ -130,18 +328,179 @@ impl Contract {
     }
 
     // 创建NFT
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn internal_mint(
         &mut self,
         account_id: &AccountId,
         token_id: &TokenId,
         metadata: &TokenMetadata,
         memo: Option<String>,
-        secret: String,
+        secret: MazeSecret,
+        royalty: Option<HashMap<AccountId, u32>>,
+        difficulty: u8,
+    ) {
+        self.internal_validate_mint(account_id, metadata, royalty.as_ref(), difficulty);
+        self.internal_write_maze(account_id, token_id, metadata, secret, royalty, difficulty);
+
+        EventLog::nft(EventLogVariant::NftMint(vec![NftMintLog {
+            // Owner of the token.
+            owner_id: account_id.clone(),
+            // Vector of token IDs that were minted.
+            token_ids: vec![token_id.to_string()],
+            // An optional memo to include.
+            memo: memo.clone(),
+        }]))
+        .emit();
+
+        MazesEventLog::new(MazesEventLogVariant::MazeCreated(vec![MazeCreatedLog {
+            creator_id: account_id.clone(),
+            token_id: token_id.clone(),
+            memo,
+        }]))
+        .emit();
+    }
+
+    // 普通账户不能设置难度, 避免自行 mint 高难度迷宫刷 MAZE
+    pub(crate) fn assert_can_set_difficulty(&self, difficulty: Option<u8>) {
+        require!(
+            difficulty.is_none()
+                || self.internal_has_role(&env::predecessor_account_id(), Role::Minter),
+            "Only contract owner or Minter can set difficulty."
+        );
+    }
+
+    // mint 前的检查, 不写入任何状态
+    pub(crate) fn internal_validate_mint(
+        &self,
+        account_id: &AccountId,
+        metadata: &TokenMetadata,
+        royalty: Option<&HashMap<AccountId, u32>>,
+        difficulty: u8,
+    ) {
+        metadata::assert_valid_token_metadata(metadata);
+        require!(
+            (1..=MAX_DIFFICULTY).contains(&difficulty),
+            format!("Difficulty must be between 1 and {}.", MAX_DIFFICULTY)
+        );
+        if let Some(royalty) = royalty {
+            assert_valid_royalty(royalty);
+        }
+        if let Some(media_hash) = &metadata.media_hash {
+            self.internal_assert_media_available(&media_hash.0, account_id);
+        }
+    }
+
+    // 猜测是否与明文 secret 或 secret 的承诺一致
+    pub(crate) fn internal_secret_matches(&self, token_id: &TokenId, guess: &str) -> bool {
+        if let Some(commitment) = self.secret_commitments.get(token_id) {
+            let salted = [commitment.salt.as_bytes(), guess.as_bytes()].concat();
+            return env::sha256(&salted) == commitment.hash;
+        }
+        self.secret.get(token_id).expect("Token not found.") == guess
+    }
+
+    // 写入迷宫的全部状态, 不记录事件, 调用前需要先调用 `internal_validate_mint`
+    pub(crate) fn internal_write_maze(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &TokenId,
+        metadata: &TokenMetadata,
+        secret: MazeSecret,
+        royalty: Option<HashMap<AccountId, u32>>,
+        difficulty: u8,
     ) {
+        if let Some(media_hash) = &metadata.media_hash {
+            self.internal_index_media(&media_hash.0, account_id, token_id);
+        }
+
         self.tokens.owner_by_id.insert(token_id, account_id);
         // 添加 secret
-        self.set_account_description(token_id.clone(), secret);
+        match secret {
+            MazeSecret::Plain(secret) => {
+                self.secret.insert(token_id.clone(), secret);
+            }
+            MazeSecret::Commitment(commitment) => {
+                self.secret_commitments.insert(token_id.clone(), commitment);
+            }
+        }
+        self.internal_set_royalty(token_id, account_id, royalty);
+        self.internal_add_token_to_creator(account_id, token_id);
+        self.mazes.insert(
+            token_id.clone(),
+            MazeState {
+                difficulty,
+                created_at: Some(env::block_timestamp()),
+                created_block: Some(env::block_height()),
+                attempts: 0,
+                rated: self.internal_has_role(&env::predecessor_account_id(), Role::Minter),
+                creator_reward_days: 0,
+            },
+        );
+        self.internal_index_maze(token_id, difficulty);
+        self.stats.minted += 1;
+
+        self.internal_add_token_to_owner(account_id, token_id);
+        self.internal_update_player_stats(account_id, |stats| stats.mazes_created += 1);
+        self.internal_award_achievements(account_id, AchievementTrigger::Minted);
 
+        if let Some(token_metadata_by_id) = &mut self.tokens.token_metadata_by_id {
+            token_metadata_by_id.insert(token_id, metadata);
+        }
+    }
+
+    // 按照 `duplicate_policy` 检查图片能否再次 mint
+    pub(crate) fn internal_assert_media_available(
+        &self,
+        media_hash: &[u8],
+        account_id: &AccountId,
+    ) {
+        if let Some(record) = self.media_index.get(media_hash) {
+            match self.duplicate_policy {
+                DuplicatePolicy::Reject => {
+                    panic!(
+                        "This image has already been minted as token {}.",
+                        record.token_id
+                    )
+                }
+                DuplicatePolicy::CreatorOnly => require!(
+                    &record.creator_id == account_id,
+                    "Only the original creator can mint this image again."
+                ),
+                DuplicatePolicy::Allow => {}
+            }
+        }
+    }
+
+    // 按照 `duplicate_policy` 检查并记录图片的使用情况
+    pub(crate) fn internal_index_media(
+        &mut self,
+        media_hash: &[u8],
+        account_id: &AccountId,
+        token_id: &TokenId,
+    ) {
+        // 调用前已按 `duplicate_policy` 检查过, 包括同一批次中的重复图片
+        self.internal_assert_media_available(media_hash, account_id);
+        if let Some(record) = self.media_index.get_mut(media_hash) {
+            record.copies += 1;
+            return;
+        }
+
+        self.media_index.insert(
+            media_hash.to_vec(),
+            MediaRecord {
+                token_id: token_id.clone(),
+                creator_id: account_id.clone(),
+                copies: 1,
+            },
+        );
+    }
+
+    // 添加 token_owner_id -> token_ids 映射
+    pub(crate) fn internal_add_token_to_owner(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &TokenId,
+    ) {
         if let Some(tokens_per_owner) = &mut self.tokens.tokens_per_owner {
             let mut token_ids = tokens_per_owner.get(account_id).unwrap_or_else(|| {
                 UnorderedSet::new(
@@ -153,40 +512,17 @@ impl Contract {
             token_ids.insert(token_id);
             tokens_per_owner.insert(account_id, &token_ids);
         }
-
-        if let Some(token_metadata_by_id) = &mut self.tokens.token_metadata_by_id {
-            token_metadata_by_id.insert(token_id, metadata);
+        if let Ok(id) = token_id.parse() {
+            self.owner_index.insert((account_id.clone(), id), ());
         }
-
-        let nft_mint_log: EventLog = EventLog {
-            // Standard name ("nep177").
-            standard: NFT_STANDARD_NAME.to_string(),
-            // Version of the standard ("nft-1.0.0").
-            version: NFT_METADATA_SPEC.to_string(),
-            // The data related with the event stored in a vector.
-            event: EventLogVariant::NftMint(vec![NftMintLog {
-                // Owner of the token.
-                owner_id: account_id.clone(),
-                // Vector of token IDs that were minted.
-                token_ids: vec![token_id.to_string()],
-                // An optional memo to include.
-                memo,
-            }]),
-        };
-
-        env::log_str(&nft_mint_log.to_string());
     }
 
-    pub(crate) fn internal_burn(
+    // 更新或移除 token_owner_id -> token_ids 映射
+    pub(crate) fn internal_remove_token_from_owner(
         &mut self,
         account_id: &AccountId,
         token_id: &TokenId,
-        memo: Option<String>,
     ) {
-        // 移除 token_id -> token_owner_id 映射
-        self.tokens.owner_by_id.remove(token_id);
-
-        // 更新或移除 token_owner_id -> token_ids 映射
         if let Some(tokens_per_owner) = &mut self.tokens.tokens_per_owner {
             if let Some(mut token_ids) = tokens_per_owner.remove(account_id) {
                 token_ids.remove(token_id);
@@ -195,10 +531,115 @@ impl Contract {
                 }
             }
         };
+        if let Ok(id) = token_id.parse() {
+            self.owner_index.remove(&(account_id.clone(), id));
+        }
+    }
+
+    // 管理员代为转移 NFT, 由 `execute_admin_action` 调用
+    pub(crate) fn internal_admin_transfer(
+        &mut self,
+        token_id: &TokenId,
+        receiver_id: &AccountId,
+        memo: Option<String>,
+    ) {
+        let old_owner_id = self
+            .tokens
+            .owner_by_id
+            .get(token_id)
+            .expect("Token not found.");
+        require!(
+            &old_owner_id != receiver_id,
+            "Current and next owner must differ."
+        );
+
+        self.internal_move_token(token_id, &old_owner_id, receiver_id);
+
+        EventLog::nft(EventLogVariant::NftTransfer(vec![NftTransferLog {
+            authorized_id: Some(env::predecessor_account_id().to_string()),
+            old_owner_id: old_owner_id.to_string(),
+            new_owner_id: receiver_id.to_string(),
+            token_ids: vec![token_id.clone()],
+            memo,
+        }]))
+        .emit();
+    }
+
+    // 转移 NFT 并清除原有授权, 与 `nft_transfer` 行为一致, 不记录事件
+    pub(crate) fn internal_move_token(
+        &mut self,
+        token_id: &TokenId,
+        old_owner_id: &AccountId,
+        receiver_id: &AccountId,
+    ) {
+        if let Some(approvals_by_id) = &mut self.tokens.approvals_by_id {
+            approvals_by_id.remove(token_id);
+        }
+        self.tokens
+            .internal_transfer_unguarded(token_id, old_owner_id, receiver_id);
+        self.internal_sync_owner_index(token_id, Some(old_owner_id.clone()));
+    }
+
+    // 销毁 NFT, 返回销毁前的所有者
+    pub(crate) fn internal_burn(&mut self, token_id: &TokenId, memo: Option<String>) -> AccountId {
+        let account_id = self.internal_remove_token(token_id);
+
+        EventLog::nft(EventLogVariant::NftBurn(vec![NftBurnLog {
+            // Account that called the burn.
+            authorized_id: Some(env::predecessor_account_id().to_string()),
+            // Owner of the token.
+            owner_id: account_id.to_string(),
+            // Vector of token IDs that were burned.
+            token_ids: vec![token_id.to_string()],
+            // An optional memo to include.
+            memo,
+        }]))
+        .emit();
+
+        account_id
+    }
+
+    // 移除 NFT 的全部状态, 不记录事件, 返回移除前的所有者
+    pub(crate) fn internal_remove_token(&mut self, token_id: &TokenId) -> AccountId {
+        // 移除 token_id -> token_owner_id 映射, 以链上记录的所有者为准
+        let account_id = &self
+            .tokens
+            .owner_by_id
+            .remove(token_id)
+            .expect("Token not found.");
+
+        // 更新或移除 token_owner_id -> token_ids 映射
+        self.internal_remove_token_from_owner(account_id, token_id);
+        self.stats.burned += 1;
 
-        // 移除 token_id -> token_metadata 映射
+        // 移除 token_id -> secret 映射
+        self.secret.remove(token_id);
+        self.secret_commitments.remove(token_id);
+
+        // 移除 token_id -> royalty 映射
+        self.royalties.remove(token_id);
+
+        // 移除 token_id -> 创建者及游戏状态映射, 索引需要在移除游戏状态之前更新
+        self.internal_unindex_maze(token_id);
+        self.internal_remove_token_from_creator(token_id);
+        self.mazes.remove(token_id);
+        self.attempts_by_account.remove(token_id);
+        self.hints.remove(token_id);
+
+        // 移除 token_id -> token_metadata 映射, 并释放该 NFT 占用的图片
         if let Some(token_metadata_by_id) = &mut self.tokens.token_metadata_by_id {
-            token_metadata_by_id.remove(token_id);
+            if let Some(media_hash) = token_metadata_by_id
+                .remove(token_id)
+                .and_then(|metadata| metadata.media_hash)
+            {
+                // 最早的 NFT 被销毁后仍保留记录, 直到所有副本都被销毁
+                if let Some(record) = self.media_index.get_mut(&media_hash.0) {
+                    record.copies -= 1;
+                    if record.copies == 0 {
+                        self.media_index.remove(&media_hash.0);
+                    }
+                }
+            }
         }
 
         // 移除 token_id -> approval_ids 映射
@@ -211,46 +652,25 @@ impl Contract {
             next_approval_id_by_id.remove(token_id);
         }
 
-        let nft_burn_log: EventLog = EventLog {
-            // Standard name ("nep171").
-            standard: NFT_STANDARD_NAME.to_string(),
-            // Version of the standard ("nft-1.0.0").
-            version: NFT_METADATA_SPEC.to_string(),
-            // The data related with the event stored in a vector.
-            event: EventLogVariant::NftBurnLog(vec![NftBurnLog {
-                authorized_id: Some(account_id.to_string()),
-                // Owner of the token.
-                owner_id: account_id.to_string(),
-                // Vector of token IDs that were minted.
-                token_ids: vec![token_id.to_string()],
-                // An optional memo to include.
-                memo,
-            }]),
-        };
+        account_id.clone()
+    }
 
-        env::log_str(&nft_burn_log.to_string());
+    // 迷宫未被解开就被销毁时, 赏金退还给出资账户, 托管的外部 NFT 退还给创建者
+    pub(crate) fn internal_refund_escrow(&mut self, token_id: &TokenId) {
+        self.internal_refund_bounty(token_id);
+        if let Some(wrapped) = self.wrapped_nfts.get(token_id) {
+            let creator_id = wrapped.creator_id.clone();
+            self.internal_release_wrapped_nft(token_id, &creator_id);
+        }
     }
 }
 
-impl_non_fungible_token_core!(Contract, tokens);
-impl_non_fungible_token_approval!(Contract, tokens);
 impl_non_fungible_token_enumeration!(Contract, tokens);
 
-pub trait MazesNonFungibleTokenMetadataProvider {
-    fn nft_metadata(&self, base_uri: Option<String>) -> NFTContractMetadata;
-}
 // 为合约实现 NEP177
 #[near_bindgen]
-impl MazesNonFungibleTokenMetadataProvider for Contract {
-    fn nft_metadata(&self, base_uri: Option<String>) -> NFTContractMetadata {
-        NFTContractMetadata {
-            spec: "nft-1.0.0".to_string(),
-            name: "NFT Mazes Contract".to_string(),
-            symbol: "Maze".to_string(),
-            icon: None,
-            base_uri,
-            reference: None,
-            reference_hash: None,
-        }
+impl NonFungibleTokenMetadataProvider for Contract {
+    fn nft_metadata(&self) -> NFTContractMetadata {
+        self.metadata.get().expect("Contract metadata is not set.")
     }
 }
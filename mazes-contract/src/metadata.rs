@@ -0,0 +1,119 @@
+use near_contract_standards::non_fungible_token::metadata::{NFTContractMetadata, TokenMetadata};
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::{env, require};
+
+pub const NFT_METADATA_SPEC: &str = "nft-1.0.0";
+
+const DATA_URI_PREFIX: &str = "data:";
+const BASE64_MARKER: &str = ";base64";
+
+/// 校验 mint 时传入的 metadata
+/// * `media` / `reference` 存在时对应的 hash 必须存在且为 32 字节
+/// * `data:` URI 会在链上解码, 并校验 sha256 是否与 hash 一致
+/// * 外部 URL 无法在链上获取内容, 只校验 hash 的格式
+pub(crate) fn assert_valid_token_metadata(metadata: &TokenMetadata) {
+    assert_content_hash(
+        metadata.media.as_deref(),
+        metadata.media_hash.as_ref(),
+        "media",
+    );
+    assert_content_hash(
+        metadata.reference.as_deref(),
+        metadata.reference_hash.as_ref(),
+        "reference",
+    );
+}
+
+/// 部署时未指定合约 metadata 时使用的默认值
+pub fn default_contract_metadata() -> NFTContractMetadata {
+    NFTContractMetadata {
+        spec: NFT_METADATA_SPEC.to_string(),
+        name: "NFT Mazes Contract".to_string(),
+        symbol: "Maze".to_string(),
+        icon: None,
+        base_uri: None,
+        reference: None,
+        reference_hash: None,
+    }
+}
+
+/// 校验合约 metadata
+/// * `spec` 必须为 `nft-1.0.0`, `name` 和 `symbol` 不能为空
+/// * `icon` 必须为 `data:` URI, 以免钱包额外请求外部资源
+/// * `reference` 与 `reference_hash` 的校验规则与 token metadata 相同
+pub(crate) fn assert_valid_contract_metadata(metadata: &NFTContractMetadata) {
+    require!(
+        metadata.spec == NFT_METADATA_SPEC,
+        format!("Spec must be {}.", NFT_METADATA_SPEC)
+    );
+    require!(!metadata.name.is_empty(), "Name cannot be empty.");
+    require!(!metadata.symbol.is_empty(), "Symbol cannot be empty.");
+    if let Some(icon) = &metadata.icon {
+        require!(
+            icon.starts_with(DATA_URI_PREFIX),
+            "Icon must be a data URI."
+        );
+    }
+    assert_content_hash(
+        metadata.reference.as_deref(),
+        metadata.reference_hash.as_ref(),
+        "reference",
+    );
+}
+
+fn assert_content_hash(uri: Option<&str>, hash: Option<&Base64VecU8>, field: &str) {
+    require!(
+        uri.is_some() || hash.is_none(),
+        format!("{}_hash is set without {}.", field, field)
+    );
+    let uri = match uri {
+        Some(uri) => uri,
+        None => return,
+    };
+    let hash = hash.unwrap_or_else(|| panic!("{}_hash is required when {} is set.", field, field));
+
+    require!(
+        hash.0.len() == 32,
+        format!("{}_hash has to be a base64 encoded sha256 hash.", field)
+    );
+
+    if let Some(payload) = decode_data_uri(uri, field) {
+        require!(
+            env::sha256(&payload) == hash.0,
+            format!("{}_hash does not match the {} content.", field, field)
+        );
+    }
+}
+
+/// 解码 `data:[<mediatype>][;base64],<data>` 格式的 URI, 非 `data:` URI 返回 `None`
+fn decode_data_uri(uri: &str, field: &str) -> Option<Vec<u8>> {
+    let rest = uri.strip_prefix(DATA_URI_PREFIX)?;
+    let (header, data) = rest
+        .split_once(',')
+        .unwrap_or_else(|| panic!("Malformed data URI in {}.", field));
+
+    let payload = if header.ends_with(BASE64_MARKER) {
+        base64::decode(data).unwrap_or_else(|_| panic!("Invalid base64 payload in {}.", field))
+    } else {
+        percent_decode(data).unwrap_or_else(|| panic!("Invalid percent-encoding in {}.", field))
+    };
+    Some(payload)
+}
+
+fn percent_decode(data: &str) -> Option<Vec<u8>> {
+    let bytes = data.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes.get(i + 1..i + 3)?;
+            let hex = std::str::from_utf8(hex).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    Some(decoded)
+}
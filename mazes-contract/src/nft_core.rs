@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use near_contract_standards::non_fungible_token::approval::NonFungibleTokenApproval;
+use near_contract_standards::non_fungible_token::core::{
+    NonFungibleTokenCore, NonFungibleTokenResolver,
+};
+use near_contract_standards::non_fungible_token::{Token, TokenId};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{near_bindgen, AccountId, Promise, PromiseOrValue};
+
+use crate::*;
+
+/// NEP-171 的 `Token`, 额外包含迷宫的创建者
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MazeToken {
+    #[serde(flatten)]
+    pub token: Token,
+    // 升级前 mint 的迷宫没有记录创建者
+    pub creator_id: Option<AccountId>,
+}
+
+// 与 `impl_non_fungible_token_core!` 相同, 额外检查暂停状态并拒绝转移奖杯
+// `nft_token` 需要返回创建者, 所以没有实现 `NonFungibleTokenCore`
+#[near_bindgen]
+impl Contract {
+    #[payable]
+    pub fn nft_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) {
+        self.assert_not_paused(PauseFeature::Transfer);
+        self.assert_not_trophy(&token_id);
+        let old_owner_id = self.tokens.owner_by_id.get(&token_id);
+        self.tokens
+            .nft_transfer(receiver_id, token_id.clone(), approval_id, memo);
+        self.internal_sync_owner_index(&token_id, old_owner_id);
+    }
+
+    #[payable]
+    pub fn nft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<bool> {
+        self.assert_not_paused(PauseFeature::Transfer);
+        self.assert_not_trophy(&token_id);
+        let old_owner_id = self.tokens.owner_by_id.get(&token_id);
+        let result =
+            self.tokens
+                .nft_transfer_call(receiver_id, token_id.clone(), approval_id, memo, msg);
+        self.internal_sync_owner_index(&token_id, old_owner_id);
+        result
+    }
+
+    pub fn nft_token(&self, token_id: TokenId) -> Option<MazeToken> {
+        self.internal_maze_token(token_id)
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_maze_token(&self, token_id: TokenId) -> Option<MazeToken> {
+        let creator_id = self.creator_by_id.get(&token_id).cloned();
+        self.tokens
+            .nft_token(token_id)
+            .map(|token| MazeToken { token, creator_id })
+    }
+}
+
+#[near_bindgen]
+impl NonFungibleTokenResolver for Contract {
+    #[private]
+    fn nft_resolve_transfer(
+        &mut self,
+        previous_owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approved_account_ids: Option<HashMap<AccountId, u64>>,
+    ) -> bool {
+        let old_owner_id = self.tokens.owner_by_id.get(&token_id);
+        let transferred = self.tokens.nft_resolve_transfer(
+            previous_owner_id,
+            receiver_id,
+            token_id.clone(),
+            approved_account_ids,
+        );
+        self.internal_sync_owner_index(&token_id, old_owner_id);
+        transferred
+    }
+}
+
+// 与 `impl_non_fungible_token_approval!` 相同, 暂停时仍然允许撤销授权, 奖杯不能授权
+#[near_bindgen]
+impl NonFungibleTokenApproval for Contract {
+    #[payable]
+    fn nft_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        msg: Option<String>,
+    ) -> Option<Promise> {
+        self.assert_not_paused(PauseFeature::Approval);
+        self.assert_not_trophy(&token_id);
+        self.tokens.nft_approve(token_id, account_id, msg)
+    }
+
+    #[payable]
+    fn nft_revoke(&mut self, token_id: TokenId, account_id: AccountId) {
+        self.tokens.nft_revoke(token_id, account_id)
+    }
+
+    #[payable]
+    fn nft_revoke_all(&mut self, token_id: TokenId) {
+        self.tokens.nft_revoke_all(token_id)
+    }
+
+    fn nft_is_approved(
+        &self,
+        token_id: TokenId,
+        approved_account_id: AccountId,
+        approval_id: Option<u64>,
+    ) -> bool {
+        self.tokens
+            .nft_is_approved(token_id, approved_account_id, approval_id)
+    }
+}
@@ -0,0 +1,205 @@
+use std::fmt::Display;
+use std::ops::Bound;
+use std::str::FromStr;
+
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{require, AccountId};
+
+use crate::*;
+
+// 分页查询单页数量的上限, 避免超出 RPC 响应大小
+pub const MAX_PAGE_LIMIT: u64 = 50;
+// 单页最多检查的 token id 数量, 查询全部 NFT 时已销毁的 id 也会计入
+pub const MAX_PAGE_SCAN: u64 = 1_000;
+
+/// 分页查询的排序方式, 按 token id (即 mint 顺序) 排序
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    OldestFirst,
+    NewestFirst,
+}
+
+/// 分页查询的结果, `next_cursor` 为 `None` 时已没有更多数据
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+// 分页参数, `cursor` 为上一页返回的 `next_cursor`, 传入时忽略 `from_index`
+pub(crate) struct PageRequest {
+    pub from_index: Option<U128>,
+    pub limit: Option<u64>,
+    pub cursor: Option<String>,
+    pub order: Option<SortOrder>,
+}
+
+// 分页位置, `skip` 为 `from_index` 中尚未跳过的数量, 单页检查数量达到上限时保留到下一页
+struct Position {
+    after: u64,
+    skip: u64,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.skip {
+            0 => write!(f, "{}", self.after),
+            skip => write!(f, "{}:{}", self.after, skip),
+        }
+    }
+}
+
+impl FromStr for Position {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(position: &str) -> Result<Self, Self::Err> {
+        let (after, skip) = position.split_once(':').unwrap_or((position, "0"));
+        Ok(Self {
+            after: after.parse()?,
+            skip: skip.parse()?,
+        })
+    }
+}
+
+impl Contract {
+    // 按分页参数返回一页 token id, `account_id` 为 `None` 时查询全部 NFT
+    // 单页最多检查 `MAX_PAGE_SCAN` 个 id, 结果不足一页时也可能有下一页
+    pub(crate) fn internal_page_token_ids(
+        &self,
+        account_id: Option<&AccountId>,
+        request: PageRequest,
+    ) -> Page<TokenId> {
+        let limit = request.limit.unwrap_or(MAX_PAGE_LIMIT);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        let limit = limit.min(MAX_PAGE_LIMIT) as usize;
+        let order = request.order.unwrap_or_default();
+        let (after, skip) = match request.cursor.as_deref().map(decode_cursor::<Position>) {
+            Some(position) => (Some(position.after), position.skip),
+            None => (
+                None,
+                u64::try_from(request.from_index.map_or(0, |index| index.0)).unwrap_or(u64::MAX),
+            ),
+        };
+
+        match account_id {
+            Some(account_id) => {
+                let ids = self.internal_owner_ids(account_id, order, after);
+                collect_ids(ids, |_| true, skip, limit)
+            }
+            None => {
+                let ids: Box<dyn Iterator<Item = u64>> = match order {
+                    SortOrder::OldestFirst => {
+                        Box::new(after.map_or(1, |after| after.saturating_add(1))..=self.unique_id)
+                    }
+                    SortOrder::NewestFirst => Box::new(
+                        (1..=after.map_or(self.unique_id, |after| {
+                            after.saturating_sub(1).min(self.unique_id)
+                        }))
+                            .rev(),
+                    ),
+                };
+                collect_ids(
+                    ids,
+                    |id| self.tokens.owner_by_id.contains_key(&id.to_string()),
+                    skip,
+                    limit,
+                )
+            }
+        }
+    }
+
+    // 按 id 顺序遍历账户持有的 NFT, 从 `after` 之后开始
+    fn internal_owner_ids<'a>(
+        &'a self,
+        account_id: &AccountId,
+        order: SortOrder,
+        after: Option<u64>,
+    ) -> Box<dyn Iterator<Item = u64> + 'a> {
+        let first = Bound::Included((account_id.clone(), 0));
+        let last = Bound::Included((account_id.clone(), u64::MAX));
+        let after = after.map(|after| Bound::Excluded((account_id.clone(), after)));
+        match order {
+            SortOrder::OldestFirst => Box::new(
+                self.owner_index
+                    .range((after.unwrap_or(first), last))
+                    .map(|((_, id), _)| *id),
+            ),
+            SortOrder::NewestFirst => Box::new(
+                self.owner_index
+                    .range((first, after.unwrap_or(last)))
+                    .rev()
+                    .map(|((_, id), _)| *id),
+            ),
+        }
+    }
+
+    // 所有者变化后更新 (所有者, id) 索引, `old_owner_id` 为变化前的所有者
+    pub(crate) fn internal_sync_owner_index(
+        &mut self,
+        token_id: &TokenId,
+        old_owner_id: Option<AccountId>,
+    ) {
+        let id = match token_id.parse::<u64>() {
+            Ok(id) => id,
+            Err(_) => return,
+        };
+        if let Some(old_owner_id) = old_owner_id {
+            self.owner_index.remove(&(old_owner_id, id));
+        }
+        if let Some(owner_id) = self.tokens.owner_by_id.get(token_id) {
+            self.owner_index.insert((owner_id, id), ());
+        }
+    }
+}
+
+// 按顺序取满一页, `exists` 为 `false` 的 id 不计入 `skip`
+// 检查数量达到 `MAX_PAGE_SCAN` 时停止, 剩余的 `skip` 保存在游标中
+fn collect_ids(
+    ids: impl Iterator<Item = u64>,
+    exists: impl Fn(u64) -> bool,
+    mut skip: u64,
+    limit: usize,
+) -> Page<TokenId> {
+    let mut items = vec![];
+    let mut last_scanned = None;
+    let mut has_more = false;
+    for (scanned, id) in ids.enumerate() {
+        if items.len() == limit || scanned as u64 >= MAX_PAGE_SCAN {
+            has_more = true;
+            break;
+        }
+        last_scanned = Some(id);
+        if exists(id) {
+            if skip > 0 {
+                skip -= 1;
+            } else {
+                items.push(id.to_string());
+            }
+        }
+    }
+
+    Page {
+        items,
+        next_cursor: last_scanned
+            .filter(|_| has_more)
+            .map(|after| encode_cursor(Position { after, skip })),
+    }
+}
+
+pub(crate) fn encode_cursor(position: impl Display) -> String {
+    base64::encode(position.to_string())
+}
+
+pub(crate) fn decode_cursor<T: FromStr>(cursor: &str) -> T {
+    base64::decode(cursor)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .and_then(|position| position.parse().ok())
+        .expect("Invalid cursor.")
+}
@@ -0,0 +1,100 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, require};
+
+use crate::*;
+
+/// 可以单独暂停的功能
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum PauseFeature {
+    // mint
+    Mint,
+    // 猜测 secret 及销毁 NFT
+    Guess,
+    // 转移 NFT
+    Transfer,
+    // 授权 NFT
+    Approval,
+}
+
+/// 各功能当前是否暂停
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PauseState {
+    pub mint: bool,
+    pub guess: bool,
+    pub transfer: bool,
+    pub approval: bool,
+}
+
+impl PauseState {
+    pub fn is_paused(&self, feature: PauseFeature) -> bool {
+        *self.flag(feature)
+    }
+
+    fn flag(&self, feature: PauseFeature) -> &bool {
+        match feature {
+            PauseFeature::Mint => &self.mint,
+            PauseFeature::Guess => &self.guess,
+            PauseFeature::Transfer => &self.transfer,
+            PauseFeature::Approval => &self.approval,
+        }
+    }
+
+    fn flag_mut(&mut self, feature: PauseFeature) -> &mut bool {
+        match feature {
+            PauseFeature::Mint => &mut self.mint,
+            PauseFeature::Guess => &mut self.guess,
+            PauseFeature::Transfer => &mut self.transfer,
+            PauseFeature::Approval => &mut self.approval,
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    // 合约所有者或 Pauser 暂停/恢复某个功能
+    pub fn set_paused(&mut self, feature: PauseFeature, paused: bool) {
+        self.assert_role(Role::Pauser);
+        let flag = self.paused.flag_mut(feature);
+        if *flag == paused {
+            return;
+        }
+        *flag = paused;
+
+        MazesEventLog::new(MazesEventLogVariant::PauseChanged(vec![PauseChangedLog {
+            account_id: env::predecessor_account_id(),
+            feature,
+            paused,
+        }]))
+        .emit();
+    }
+
+    pub fn view_pause_state(&self) -> PauseState {
+        self.paused
+    }
+}
+
+impl Contract {
+    pub(crate) fn assert_not_paused(&self, feature: PauseFeature) {
+        require!(
+            !self.paused.is_paused(feature),
+            format!("{:?} is paused.", feature)
+        );
+    }
+}
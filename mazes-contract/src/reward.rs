@@ -0,0 +1,186 @@
+use near_contract_standards::fungible_token::events::{FtBurn, FtMint};
+use near_contract_standards::fungible_token::metadata::{
+    FungibleTokenMetadata, FungibleTokenMetadataProvider, FT_METADATA_SPEC,
+};
+use near_contract_standards::non_fungible_token::TokenId;
+use near_contract_standards::{impl_fungible_token_core, impl_fungible_token_storage};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, require, AccountId, Balance, PromiseOrValue};
+
+use crate::*;
+
+// MAZE 的精度
+pub const MAZE_DECIMALS: u8 = 18;
+// 每个难度等级的默认解谜奖励 (10 MAZE)
+pub const DEFAULT_SOLVER_REWARD: Balance = 10 * 10u128.pow(MAZE_DECIMALS as u32);
+// 迷宫每存活一天, 创建者默认获得的奖励 (1 MAZE)
+pub const DEFAULT_CREATOR_DAILY_REWARD: Balance = 10u128.pow(MAZE_DECIMALS as u32);
+// 创建者奖励最多计算的天数
+pub const MAX_CREATOR_REWARD_DAYS: u64 = 365;
+
+pub(crate) const NANOSECONDS_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+/// MAZE 的发放数量, 由合约所有者或 Treasurer 调整
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardConfig {
+    // 解谜者按难度等级获得的奖励, 实际奖励为 `solver_reward * difficulty`
+    pub solver_reward: U128,
+    // 迷宫每存活一整天创建者获得的奖励, 可以通过 `claim_creator_reward` 随时领取, 解开时发放未领取的部分
+    pub creator_daily_reward: U128,
+}
+
+impl Default for RewardConfig {
+    fn default() -> Self {
+        Self {
+            solver_reward: U128(DEFAULT_SOLVER_REWARD),
+            creator_daily_reward: U128(DEFAULT_CREATOR_DAILY_REWARD),
+        }
+    }
+}
+
+pub fn default_ft_metadata() -> FungibleTokenMetadata {
+    FungibleTokenMetadata {
+        spec: FT_METADATA_SPEC.to_string(),
+        name: "Maze Reward".to_string(),
+        symbol: "MAZE".to_string(),
+        icon: None,
+        reference: None,
+        reference_hash: None,
+        decimals: MAZE_DECIMALS,
+    }
+}
+
+// 为合约实现 NEP141 及 NEP145
+impl_fungible_token_core!(Contract, ft);
+impl_fungible_token_storage!(Contract, ft, on_reward_account_closed);
+
+// 为合约实现 NEP148
+#[near_bindgen]
+impl FungibleTokenMetadataProvider for Contract {
+    fn ft_metadata(&self) -> FungibleTokenMetadata {
+        self.ft_metadata.get().expect("Token metadata is not set.")
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn set_reward_config(&mut self, config: RewardConfig) {
+        self.assert_role(Role::Treasurer);
+        self.reward_config = config;
+    }
+
+    pub fn view_reward_config(&self) -> RewardConfig {
+        self.reward_config.clone()
+    }
+
+    // 创建者领取尚未解开的迷宫已存活天数的奖励, 返回本次发放的 MAZE
+    pub fn claim_creator_reward(&mut self, token_id: TokenId) -> U128 {
+        let creator_id = env::predecessor_account_id();
+        require!(
+            self.tokens.owner_by_id.contains_key(&token_id),
+            "Token not found."
+        );
+        require!(
+            self.creator_by_id.get(&token_id) == Some(&creator_id),
+            "Only the creator can claim the reward."
+        );
+        require!(
+            self.ft.accounts.contains_key(&creator_id),
+            "Account is not registered."
+        );
+        let state = self.mazes.get(&token_id).cloned().unwrap_or_default();
+        require!(state.rated, "Only rated mazes earn rewards.");
+
+        let amount = self.internal_reward_creator(&token_id, &creator_id);
+        U128(amount)
+    }
+
+    // 合约所有者更新 MAZE 的 metadata
+    pub fn set_ft_metadata(&mut self, metadata: FungibleTokenMetadata) {
+        self.assert_owner();
+        metadata.assert_valid();
+        self.ft_metadata.set(&metadata);
+    }
+}
+
+impl Contract {
+    // 账户强制注销时剩余的 MAZE 被销毁, 标准实现不会记录 `ft_burn` 事件
+    fn on_reward_account_closed(&mut self, account_id: AccountId, balance: Balance) {
+        if balance > 0 {
+            FtBurn {
+                owner_id: &account_id,
+                amount: &U128(balance),
+                memo: Some("Account closed"),
+            }
+            .emit();
+        }
+    }
+
+    // 迷宫的创建者或当前所有者猜中, 需要在销毁 NFT 之前调用
+    pub(crate) fn internal_is_self_solve(&self, token_id: &TokenId, solver_id: &AccountId) -> bool {
+        self.creator_by_id.get(token_id) == Some(solver_id)
+            || self.tokens.owner_by_id.get(token_id).as_ref() == Some(solver_id)
+    }
+
+    // 迷宫被解开时发放 MAZE, 需要在销毁 NFT 前调用
+    // 只有合约所有者或 Minter 评定过难度的迷宫发放奖励, 创建者或所有者解开自己的迷宫不会获得任何奖励
+    pub(crate) fn internal_reward_solve(&mut self, token_id: &TokenId, solver_id: &AccountId) {
+        let state = self.mazes.get(token_id).cloned().unwrap_or_default();
+        if !state.rated || self.internal_is_self_solve(token_id, solver_id) {
+            return;
+        }
+
+        let solver_reward = self.reward_config.solver_reward.0 * u128::from(state.difficulty);
+        self.internal_mint_reward(solver_id, solver_reward, "Maze solved");
+
+        if let Some(creator_id) = self.creator_by_id.get(token_id).cloned() {
+            self.internal_reward_creator(token_id, &creator_id);
+        }
+    }
+
+    // 向创建者发放尚未发放的存活天数的奖励, 托管 NFT 的迷宫过期后不再计算天数
+    // 升级前 mint 的迷宫不知道存活了多久, 不发放
+    fn internal_reward_creator(&mut self, token_id: &TokenId, creator_id: &AccountId) -> Balance {
+        let mut state = self.mazes.get(token_id).cloned().unwrap_or_default();
+        let created_at = match state.created_at {
+            Some(created_at) => created_at,
+            None => return 0,
+        };
+        let end = self
+            .wrapped_nfts
+            .get(token_id)
+            .map_or(env::block_timestamp(), |wrapped| {
+                wrapped.expires_at.0.min(env::block_timestamp())
+            });
+        let days =
+            (end.saturating_sub(created_at) / NANOSECONDS_PER_DAY).min(MAX_CREATOR_REWARD_DAYS);
+        let unpaid_days = days.saturating_sub(state.creator_reward_days);
+        if unpaid_days == 0 || !self.ft.accounts.contains_key(creator_id) {
+            return 0;
+        }
+
+        let amount = self.reward_config.creator_daily_reward.0 * u128::from(unpaid_days);
+        state.creator_reward_days = days;
+        self.mazes.insert(token_id.clone(), state);
+        self.internal_mint_reward(creator_id, amount, "Maze survived");
+        amount
+    }
+
+    // 账户需要先通过 `storage_deposit` 注册, 未注册的账户不会获得奖励
+    fn internal_mint_reward(&mut self, account_id: &AccountId, amount: Balance, memo: &str) {
+        if amount == 0 || !self.ft.accounts.contains_key(account_id) {
+            return;
+        }
+        self.ft.internal_deposit(account_id, amount);
+
+        FtMint {
+            owner_id: account_id,
+            amount: &U128(amount),
+            memo: Some(memo),
+        }
+        .emit();
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use near_contract_standards::non_fungible_token::{refund_approved_account_ids, TokenId};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{assert_one_yocto, env, near_bindgen, require, AccountId};
+
+use crate::*;
+
+// 版税以万分比 (basis points) 记录
+pub const ROYALTY_DENOMINATOR: u32 = 10_000;
+// 单个 NFT 的版税总和上限 (50%)
+pub const MAX_TOTAL_ROYALTY: u32 = 5_000;
+// 单个 NFT 的版税账户上限, 避免 payout 超出 gas 限制
+pub const MAX_ROYALTY_ACCOUNTS: usize = 10;
+// 未指定版税时创建者默认获得的版税 (5%)
+pub const DEFAULT_CREATOR_ROYALTY: u32 = 500;
+
+/// NEP-199 中 `nft_payout` 返回的分账结果
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Payout {
+    pub payout: HashMap<AccountId, U128>,
+}
+
+pub trait NonFungibleTokenPayout {
+    fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: Option<u32>) -> Payout;
+
+    fn nft_transfer_payout(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        balance: U128,
+        max_len_payout: Option<u32>,
+    ) -> Payout;
+}
+
+// 为合约实现 NEP199
+#[near_bindgen]
+impl NonFungibleTokenPayout for Contract {
+    fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: Option<u32>) -> Payout {
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .expect("Token not found.");
+        self.internal_payout(&token_id, &owner_id, balance.0, max_len_payout)
+    }
+
+    #[payable]
+    fn nft_transfer_payout(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        balance: U128,
+        max_len_payout: Option<u32>,
+    ) -> Payout {
+        assert_one_yocto();
+        self.assert_not_paused(PauseFeature::Transfer);
+        self.assert_not_trophy(&token_id);
+        let sender_id = env::predecessor_account_id();
+        let (owner_id, approved_account_ids) =
+            self.tokens
+                .internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo);
+        self.internal_sync_owner_index(&token_id, Some(owner_id.clone()));
+
+        // 退还授权占用的存储费
+        if let Some(approved_account_ids) = approved_account_ids {
+            refund_approved_account_ids(owner_id.clone(), &approved_account_ids);
+        }
+
+        self.internal_payout(&token_id, &owner_id, balance.0, max_len_payout)
+    }
+}
+
+pub(crate) fn assert_valid_royalty(royalty: &HashMap<AccountId, u32>) {
+    require!(
+        royalty.len() <= MAX_ROYALTY_ACCOUNTS,
+        format!(
+            "Cannot have more than {} royalty accounts.",
+            MAX_ROYALTY_ACCOUNTS
+        )
+    );
+    let total = royalty
+        .values()
+        .try_fold(0u32, |total, &value| total.checked_add(value))
+        .unwrap_or(u32::MAX);
+    require!(
+        total <= MAX_TOTAL_ROYALTY,
+        format!(
+            "Total royalty cannot exceed {} basis points.",
+            MAX_TOTAL_ROYALTY
+        )
+    );
+}
+
+impl Contract {
+    // 记录 mint 时的版税, 未指定时创建者获得默认版税, 调用前需要先校验
+    pub(crate) fn internal_set_royalty(
+        &mut self,
+        token_id: &TokenId,
+        creator_id: &AccountId,
+        royalty: Option<HashMap<AccountId, u32>>,
+    ) {
+        let royalty = royalty
+            .unwrap_or_else(|| HashMap::from([(creator_id.clone(), DEFAULT_CREATOR_ROYALTY)]));
+        if !royalty.is_empty() {
+            self.royalties.insert(token_id.clone(), royalty);
+        }
+    }
+
+    // 按版税计算分账, 余额全部归当前所有者
+    pub(crate) fn internal_payout(
+        &self,
+        token_id: &TokenId,
+        owner_id: &AccountId,
+        balance: u128,
+        max_len_payout: Option<u32>,
+    ) -> Payout {
+        let mut payout = HashMap::new();
+        let mut remaining = balance;
+
+        if let Some(royalty) = self.royalties.get(token_id) {
+            for (account_id, &value) in royalty.iter() {
+                let amount = balance
+                    .checked_mul(u128::from(value))
+                    .expect("Balance is too large to calculate royalties.")
+                    / u128::from(ROYALTY_DENOMINATOR);
+                remaining -= amount;
+                *payout.entry(account_id.clone()).or_insert(0u128) += amount;
+            }
+        }
+        *payout.entry(owner_id.clone()).or_insert(0u128) += remaining;
+
+        if let Some(max_len_payout) = max_len_payout {
+            require!(
+                payout.len() <= max_len_payout as usize,
+                "Market cannot payout to that many receivers."
+            );
+        }
+
+        Payout {
+            payout: payout
+                .into_iter()
+                .map(|(account_id, amount)| (account_id, U128(amount)))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{near_bindgen, AccountId};
+
+use crate::*;
+
+/// 账户的游戏统计
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PlayerStats {
+    // 猜测次数, 包括猜中的
+    pub guesses: u32,
+    pub failed_guesses: u32,
+    // 解开的迷宫数量
+    pub solves: u32,
+    // 创建的迷宫数量
+    pub mazes_created: u32,
+}
+
+/// 合约的全局统计, 在每次状态变化时更新
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractStats {
+    pub minted: u64,
+    // 销毁的迷宫数量, 包括被解开的
+    pub burned: u64,
+    pub solved: u64,
+    // 猜测次数, 包括猜中的
+    pub guesses: u64,
+    pub failed_guesses: u64,
+    // 代币合约 -> 尚未支付或退还的赏金
+    pub active_bounty: HashMap<AccountId, U128>,
+    // 有过游戏记录 (猜测或创建迷宫) 的账户数量
+    pub players: u64,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn view_stats(&self) -> ContractStats {
+        self.stats.clone()
+    }
+
+    pub fn view_player_stats(&self, account_id: AccountId) -> PlayerStats {
+        self.player_stats
+            .get(&account_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_update_player_stats(
+        &mut self,
+        account_id: &AccountId,
+        update: impl FnOnce(&mut PlayerStats),
+    ) {
+        let mut stats = self.view_player_stats(account_id.clone());
+        update(&mut stats);
+        if self
+            .player_stats
+            .insert(account_id.clone(), stats)
+            .is_none()
+        {
+            self.stats.players += 1;
+        }
+    }
+
+    // 赏金增加时 `added` 为 `true`, 支付或退还时为 `false`
+    pub(crate) fn internal_update_active_bounty(
+        &mut self,
+        ft_contract_id: &AccountId,
+        amount: u128,
+        added: bool,
+    ) {
+        let active = self
+            .stats
+            .active_bounty
+            .entry(ft_contract_id.clone())
+            .or_insert(U128(0));
+        active.0 = if added {
+            active.0.saturating_add(amount)
+        } else {
+            active.0.saturating_sub(amount)
+        };
+        if active.0 == 0 {
+            self.stats.active_bounty.remove(ft_contract_id);
+        }
+    }
+}
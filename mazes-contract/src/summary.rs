@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use near_contract_standards::non_fungible_token::core::NonFungibleTokenCore;
+use near_contract_standards::non_fungible_token::{Token, TokenId};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, AccountId};
+
+use crate::*;
+
+/// 迷宫当前能否被猜测
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum MazeStatus {
+    Open,
+    // 已被解开, NFT 已销毁
+    Solved,
+    // 托管 NFT 的迷宫超过过期时间后不能再被猜测
+    Expired,
+}
+
+/// 迷宫的概要信息, 不包含图片内容, 用于列表展示
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MazeSummary {
+    pub token_id: TokenId,
+    pub owner_id: AccountId,
+    pub creator_id: Option<AccountId>,
+    pub title: Option<String>,
+    pub media_hash: Option<Base64VecU8>,
+    // 代币合约 -> 赏金总额
+    pub bounty: HashMap<AccountId, U128>,
+    pub status: MazeStatus,
+    pub attempts: u32,
+    pub expires_at: Option<U64>,
+}
+
+/// 猜中迷宫的方式
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug,
+)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum SolveMode {
+    // 提交与 mint 时相同的 secret
+    Secret,
+    // 提交的 secret 与 salt 拼接后的 sha256 与托管 NFT 时的承诺一致, salt 不会公开
+    Commitment,
+}
+
+/// 迷宫的完整信息, 包含 NEP-171 的 `Token` 和游戏状态, 不包含 secret
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MazeView {
+    // 已解开的迷宫 NFT 已销毁, 为 `None`
+    pub token: Option<Token>,
+    pub creator_id: Option<AccountId>,
+    pub status: MazeStatus,
+    // 解开迷宫的账户, 未解开时为 `None`
+    pub solver_id: Option<AccountId>,
+    pub solve_mode: SolveMode,
+    pub difficulty: u8,
+    // 代币合约 -> 赏金总额
+    pub bounty: HashMap<AccountId, U128>,
+    // 每次猜测收取的费用, 目前猜测免费, 始终为 0
+    pub guess_fee: U128,
+    pub attempts: u32,
+    // `viewer` 猜测的次数, 未传 `viewer` 或迷宫已解开时为 `None`
+    pub viewer_attempts: Option<u32>,
+    // `viewer` 距离下次可以猜测的时间 (纳秒), 目前不限制猜测间隔, 始终为 0
+    pub cooldown_remaining: U64,
+    // 所有者已公开的提示, 按公开顺序排列
+    pub hints: Vec<String>,
+    pub expires_at: Option<U64>,
+}
+
+#[near_bindgen]
+impl Contract {
+    // 查询迷宫及其游戏状态, 已解开的迷宫返回解开时记录的信息, 不存在或被所有者销毁时返回 `None`
+    // 解开记录超出 `MAX_SOLVED_MAZES` 被移除后, 只返回状态, 难度和猜中方式
+    pub fn view_maze(&self, token_id: TokenId, viewer: Option<AccountId>) -> Option<MazeView> {
+        let token = match self.tokens.nft_token(token_id.clone()) {
+            Some(token) => token,
+            None => {
+                let (difficulty, solve_mode, solved) = self.internal_solved_maze(&token_id)?;
+                return Some(MazeView {
+                    token: None,
+                    creator_id: solved.and_then(|solved| solved.creator_id.clone()),
+                    status: MazeStatus::Solved,
+                    solver_id: solved.map(|solved| solved.solver_id.clone()),
+                    solve_mode,
+                    difficulty,
+                    bounty: solved
+                        .map(|solved| solved.bounty.clone())
+                        .unwrap_or_default(),
+                    guess_fee: U128(0),
+                    attempts: solved.map_or(0, |solved| solved.attempts),
+                    viewer_attempts: None,
+                    cooldown_remaining: U64(0),
+                    hints: vec![],
+                    expires_at: None,
+                });
+            }
+        };
+        let state = self.mazes.get(&token_id).cloned().unwrap_or_default();
+        let viewer_attempts = viewer.map(|viewer| {
+            self.attempts_by_account
+                .get(&token_id)
+                .and_then(|attempts| attempts.get(&viewer).copied())
+                .unwrap_or(0)
+        });
+
+        Some(MazeView {
+            token: Some(token),
+            creator_id: self.creator_by_id.get(&token_id).cloned(),
+            status: self.internal_maze_status(&token_id),
+            solver_id: None,
+            solve_mode: self.internal_solve_mode(&token_id),
+            difficulty: state.difficulty,
+            bounty: self.view_bounty(token_id.clone()),
+            guess_fee: U128(0),
+            attempts: state.attempts,
+            viewer_attempts,
+            cooldown_remaining: U64(0),
+            hints: self.hints.get(&token_id).cloned().unwrap_or_default(),
+            expires_at: self
+                .wrapped_nfts
+                .get(&token_id)
+                .map(|wrapped| wrapped.expires_at),
+        })
+    }
+
+    // 分页参数与 `view_all_nft` 相同
+    pub fn view_maze_summaries(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        cursor: Option<String>,
+        order: Option<SortOrder>,
+    ) -> Page<MazeSummary> {
+        let page = self.internal_page_token_ids(
+            None,
+            PageRequest {
+                from_index,
+                limit,
+                cursor,
+                order,
+            },
+        );
+        self.internal_page_maze_summaries(page)
+    }
+
+    // 分页参数与 `view_nft_by_id` 相同
+    pub fn view_maze_summaries_by_owner(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        cursor: Option<String>,
+        order: Option<SortOrder>,
+    ) -> Page<MazeSummary> {
+        let page = self.internal_page_token_ids(
+            Some(&account_id),
+            PageRequest {
+                from_index,
+                limit,
+                cursor,
+                order,
+            },
+        );
+        self.internal_page_maze_summaries(page)
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_maze_summary(&self, token_id: TokenId) -> Option<MazeSummary> {
+        let owner_id = self.tokens.owner_by_id.get(&token_id)?;
+        let metadata = self
+            .tokens
+            .token_metadata_by_id
+            .as_ref()
+            .and_then(|by_id| by_id.get(&token_id));
+        let expires_at = self
+            .wrapped_nfts
+            .get(&token_id)
+            .map(|wrapped| wrapped.expires_at);
+
+        Some(MazeSummary {
+            owner_id,
+            creator_id: self.creator_by_id.get(&token_id).cloned(),
+            title: metadata
+                .as_ref()
+                .and_then(|metadata| metadata.title.clone()),
+            media_hash: metadata.and_then(|metadata| metadata.media_hash),
+            bounty: self.view_bounty(token_id.clone()),
+            status: self.internal_maze_status(&token_id),
+            attempts: self.mazes.get(&token_id).map_or(0, |state| state.attempts),
+            expires_at,
+            token_id,
+        })
+    }
+
+    pub(crate) fn internal_solve_mode(&self, token_id: &TokenId) -> SolveMode {
+        if self.secret_commitments.contains_key(token_id) {
+            SolveMode::Commitment
+        } else {
+            SolveMode::Secret
+        }
+    }
+
+    pub(crate) fn internal_maze_status(&self, token_id: &TokenId) -> MazeStatus {
+        match self.wrapped_nfts.get(token_id) {
+            Some(wrapped) if env::block_timestamp() >= wrapped.expires_at.0 => MazeStatus::Expired,
+            _ => MazeStatus::Open,
+        }
+    }
+
+    fn internal_page_maze_summaries(&self, page: Page<TokenId>) -> Page<MazeSummary> {
+        Page {
+            items: page
+                .items
+                .into_iter()
+                .filter_map(|token_id| self.internal_maze_summary(token_id))
+                .collect(),
+            next_cursor: page.next_cursor,
+        }
+    }
+}
@@ -1,21 +1,43 @@
 #[cfg(test)]
 mod test {
-    use crate::Contract;
+    use std::collections::HashMap;
+
+    use crate::{
+        AchievementCondition, AchievementDefinition, AdminActionStatus, Contract, ContractV0,
+        DuplicatePolicy, InvariantViolation, MazeFilter, MazeSortBy, MazeStatus, MazeSummary,
+        MazeToken, MintRequest, NonFungibleTokenPayout, Page, PauseFeature, Role, SolveMode,
+        SolvedMaze, SortOrder, StorageKey, CURRENT_STATE_VERSION, DEFAULT_ADMIN_TIMELOCK,
+        DEFAULT_CREATOR_DAILY_REWARD, DEFAULT_SOLVER_REWARD, MAX_PAGE_SCAN, MAX_SOLVED_MAZES,
+    };
+    use near_contract_standards::fungible_token::core::FungibleTokenCore;
+    use near_contract_standards::fungible_token::metadata::FungibleTokenMetadataProvider;
+    use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
     use near_contract_standards::non_fungible_token::approval::NonFungibleTokenApproval;
+    use near_contract_standards::storage_management::StorageManagement;
 
-    use near_contract_standards::non_fungible_token::core::NonFungibleTokenCore;
+    use near_contract_standards::non_fungible_token::core::NonFungibleTokenReceiver;
     use near_contract_standards::non_fungible_token::enumeration::NonFungibleTokenEnumeration;
-    use near_contract_standards::non_fungible_token::metadata::TokenMetadata;
-    use near_contract_standards::non_fungible_token::TokenId;
+    use near_contract_standards::non_fungible_token::metadata::{
+        NonFungibleTokenMetadataProvider, TokenMetadata,
+    };
+    use near_contract_standards::non_fungible_token::{NonFungibleToken, TokenId};
 
-    use near_sdk::json_types::U128;
-    use near_sdk::test_utils::VMContextBuilder;
-    use near_sdk::{testing_env, AccountId, ONE_NEAR, ONE_YOCTO};
+    use near_sdk::json_types::{Base64VecU8, U128, U64};
+    use near_sdk::store::LookupMap;
+    use near_sdk::test_utils::{get_logs, VMContextBuilder};
+    use near_sdk::{
+        env, serde_json, testing_env, AccountId, Gas, PromiseResult, RuntimeFeesConfig, VMConfig,
+        ONE_NEAR, ONE_YOCTO,
+    };
 
     fn owner() -> AccountId {
         "owner.near".parse().unwrap()
     }
 
+    fn contract_account() -> AccountId {
+        "mazes.near".parse().unwrap()
+    }
+
     fn alice() -> AccountId {
         "alice.near".parse().unwrap()
     }
@@ -24,6 +46,10 @@ mod test {
         "bob.near".parse().unwrap()
     }
 
+    fn secret() -> String {
+        "a1b2".to_string()
+    }
+
     fn token(token_id: TokenId) -> TokenMetadata {
         TokenMetadata {
             title: Some(format!("HelloNFT #{}", token_id)),
@@ -41,9 +67,63 @@ mod test {
         }
     }
 
+    fn token_with_media(token_id: TokenId, content: &[u8]) -> TokenMetadata {
+        let mut metadata = token(token_id);
+        metadata.media = Some(format!(
+            "data:text/plain;base64,{}",
+            base64::encode(content)
+        ));
+        metadata.media_hash = Some(Base64VecU8(env::sha256(content)));
+        metadata
+    }
+
+    fn nft_contract() -> AccountId {
+        "nft.near".parse().unwrap()
+    }
+
+    // 由外部合约 `nft.near` 把 Alice 的 NFT "42" 包装成迷宫
+    fn wrap_external_nft(contract: &mut Contract) {
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(contract_account())
+            .predecessor_account_id(nft_contract())
+            .build());
+
+        let secret_hash = env::sha256(format!("salt{}", secret()).as_bytes());
+        let msg = serde_json::json!({
+            "secret_hash": Base64VecU8(secret_hash),
+            "salt": "salt",
+            "metadata": token("1".to_string()),
+        });
+        contract.nft_on_transfer(alice(), alice(), "42".to_string(), msg.to_string());
+    }
+
+    fn ft_contract() -> AccountId {
+        "ft.near".parse().unwrap()
+    }
+
+    // 由代币合约 `ft.near` 转入 `amount` 作为 `token_id` 的赏金
+    fn fund_bounty(contract: &mut Contract, sender_id: AccountId, token_id: &str, amount: u128) {
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(contract_account())
+            .predecessor_account_id(ft_contract())
+            .build());
+
+        let msg = serde_json::json!({ "token_id": token_id });
+        contract.ft_on_transfer(sender_id, U128(amount), msg.to_string());
+    }
+
+    fn execute_after_timelock(contract: &mut Contract, executor: AccountId, action_id: u64) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(executor)
+            .block_timestamp(DEFAULT_ADMIN_TIMELOCK)
+            .build());
+
+        contract.execute_admin_action(action_id);
+    }
+
     #[test]
     fn test_mint_transfer_burn() {
-        let mut contract = Contract::init(owner());
+        let mut contract = Contract::init(owner(), None);
 
         let token_id_1 = "1".to_string();
         let token_1 = token(token_id_1.clone());
@@ -56,15 +136,23 @@ mod test {
             .predecessor_account_id(owner())
             .build());
 
-        contract.mint(bob(), token_1, None);
-        contract.mint(bob(), token_2, None);
+        contract.mint(bob(), token_1, secret(), None, None, None);
+        contract.mint(bob(), token_2, secret(), None, None, None);
 
         assert_eq!(
-            contract.nft_token(token_id_1.clone()).unwrap().owner_id,
+            contract
+                .nft_token(token_id_1.clone())
+                .unwrap()
+                .token
+                .owner_id,
             bob()
         );
         assert_eq!(
-            contract.nft_token(token_id_2.clone()).unwrap().owner_id,
+            contract
+                .nft_token(token_id_2.clone())
+                .unwrap()
+                .token
+                .owner_id,
             bob()
         );
         assert_eq!(contract.nft_total_supply(), U128(2));
@@ -80,11 +168,19 @@ mod test {
         contract.nft_transfer(alice(), token_id_1.clone(), None, None);
 
         assert_eq!(
-            contract.nft_token(token_id_1.clone()).unwrap().owner_id,
+            contract
+                .nft_token(token_id_1.clone())
+                .unwrap()
+                .token
+                .owner_id,
             alice()
         );
         assert_eq!(
-            contract.nft_token(token_id_2.clone()).unwrap().owner_id,
+            contract
+                .nft_token(token_id_2.clone())
+                .unwrap()
+                .token
+                .owner_id,
             bob()
         );
         assert_eq!(contract.nft_total_supply(), U128(2));
@@ -95,16 +191,21 @@ mod test {
             .predecessor_account_id(owner())
             .build());
 
-        contract.burn(bob(), token_id_2.clone(), None);
+        let action_id = contract.burn(token_id_2.clone(), None);
+        assert!(contract.nft_token(token_id_2.clone()).is_some());
+        execute_after_timelock(&mut contract, owner(), action_id);
 
-        assert_eq!(contract.nft_token(token_id_1).unwrap().owner_id, alice());
+        assert_eq!(
+            contract.nft_token(token_id_1).unwrap().token.owner_id,
+            alice()
+        );
         assert!(contract.nft_token(token_id_2).is_none());
         assert_eq!(contract.nft_total_supply(), U128(1));
     }
 
     #[test]
     fn test_approve_transfer() {
-        let mut contract = Contract::init(owner());
+        let mut contract = Contract::init(owner(), None);
 
         let token_id = "1".to_string();
         let token = token(token_id.clone());
@@ -115,10 +216,10 @@ mod test {
             .predecessor_account_id(owner())
             .build());
 
-        contract.mint(bob(), token, None);
+        contract.mint(bob(), token, secret(), None, None, None);
 
         assert_eq!(
-            contract.nft_token(token_id.clone()).unwrap().owner_id,
+            contract.nft_token(token_id.clone()).unwrap().token.owner_id,
             bob()
         );
 
@@ -145,9 +246,2120 @@ mod test {
         contract.nft_transfer(alice(), token_id.clone(), None, None);
 
         assert_eq!(
-            contract.nft_token(token_id.clone()).unwrap().owner_id,
+            contract.nft_token(token_id.clone()).unwrap().token.owner_id,
             alice()
         );
         assert!(!contract.nft_is_approved(token_id, alice(), None));
     }
+
+    #[test]
+    fn test_mint_with_data_uri_media() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(
+            bob(),
+            token_with_media("1".to_string(), b"maze"),
+            secret(),
+            None,
+            None,
+            None,
+        );
+
+        assert_eq!(
+            contract.nft_token("1".to_string()).unwrap().token.owner_id,
+            bob()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "media_hash does not match the media content.")]
+    fn test_mint_with_mismatched_media_hash() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        let mut metadata = token("1".to_string());
+        metadata.media = Some("data:text/plain;base64,bWF6ZQ==".to_string());
+        metadata.media_hash = Some(Base64VecU8(env::sha256(b"another maze")));
+        contract.mint(bob(), metadata, secret(), None, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "reference_hash is required when reference is set.")]
+    fn test_mint_external_reference_without_hash() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        let mut metadata = token("1".to_string());
+        metadata.reference = Some("https://example.com/maze.json".to_string());
+        contract.mint(bob(), metadata, secret(), None, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "This image has already been minted as token 1.")]
+    fn test_mint_duplicate_media_rejected() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(
+            bob(),
+            token_with_media("1".to_string(), b"maze"),
+            secret(),
+            None,
+            None,
+            None,
+        );
+        contract.mint(
+            alice(),
+            token_with_media("2".to_string(), b"maze"),
+            secret(),
+            None,
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    fn test_media_freed_after_burn() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        let media_hash = Base64VecU8(env::sha256(b"maze"));
+        contract.mint(
+            bob(),
+            token_with_media("1".to_string(), b"maze"),
+            secret(),
+            None,
+            None,
+            None,
+        );
+        assert_eq!(
+            contract.view_token_by_media_hash(media_hash.clone()),
+            Some("1".to_string())
+        );
+
+        let action_id = contract.burn("1".to_string(), None);
+        execute_after_timelock(&mut contract, owner(), action_id);
+        assert_eq!(contract.view_token_by_media_hash(media_hash.clone()), None);
+
+        contract.mint(
+            alice(),
+            token_with_media("2".to_string(), b"maze"),
+            secret(),
+            None,
+            None,
+            None,
+        );
+        assert_eq!(
+            contract.view_token_by_media_hash(media_hash),
+            Some("2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_mint_duplicate_media_creator_only() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.set_duplicate_policy(DuplicatePolicy::CreatorOnly);
+        contract.mint(
+            bob(),
+            token_with_media("1".to_string(), b"maze"),
+            secret(),
+            None,
+            None,
+            None,
+        );
+        contract.mint(
+            bob(),
+            token_with_media("2".to_string(), b"maze"),
+            secret(),
+            None,
+            None,
+            None,
+        );
+
+        assert_eq!(
+            contract.nft_token("2".to_string()).unwrap().token.owner_id,
+            bob()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the original creator can mint this image again.")]
+    fn test_media_kept_while_copies_remain() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.set_duplicate_policy(DuplicatePolicy::CreatorOnly);
+        for id in ["1", "2"] {
+            contract.mint(
+                bob(),
+                token_with_media(id.to_string(), b"maze"),
+                secret(),
+                None,
+                None,
+                None,
+            );
+        }
+
+        // ------------------------------ 最早的 NFT 被解开后副本仍占用图片 ----------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        contract.mint(
+            alice(),
+            token_with_media("3".to_string(), b"maze"),
+            secret(),
+            None,
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    fn test_user_burn_removes_token_from_real_owner() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(bob(), token("1".to_string()), secret(), None, None, None);
+
+        // ------------------------------ Alice 猜中 secret 销毁 Bob 的 NFT ----------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+
+        assert!(contract.nft_token("1".to_string()).is_none());
+        assert_eq!(contract.nft_supply_for_owner(bob()), U128(0));
+        assert!(contract.nft_tokens_for_owner(bob(), None, None).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Token not found.")]
+    fn test_burn_missing_token() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.burn("1".to_string(), None);
+    }
+
+    #[test]
+    fn test_check_and_repair_invariants() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        contract.mint(bob(), token("2".to_string()), secret(), None, None, None);
+        assert!(contract.check_invariants(None, None, None).is_empty());
+
+        // 模拟旧版 burn 只删除了 owner_by_id 和 metadata
+        contract.tokens.owner_by_id.remove(&"1".to_string());
+        contract
+            .tokens
+            .token_metadata_by_id
+            .as_mut()
+            .unwrap()
+            .remove(&"1".to_string());
+
+        let expected = vec![
+            InvariantViolation::OrphanedSecret {
+                token_id: "1".to_string(),
+            },
+            InvariantViolation::StaleOwnerSetEntry {
+                token_id: "1".to_string(),
+                account_id: alice(),
+            },
+        ];
+        // Alice 已没有任何 NFT, 需要显式检查她的集合
+        let account_ids = Some(vec![alice(), bob()]);
+        assert_eq!(
+            contract.check_invariants(None, None, account_ids.clone()),
+            expected
+        );
+        assert_eq!(
+            contract.repair_indexes(None, None, account_ids.clone()),
+            expected
+        );
+        assert!(contract
+            .check_invariants(None, None, account_ids)
+            .is_empty());
+        assert_eq!(contract.nft_supply_for_owner(alice()), U128(0));
+        assert_eq!(contract.nft_supply_for_owner(bob()), U128(1));
+    }
+
+    #[test]
+    fn test_nep171_events() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.set_admin_timelock(U64(0));
+        contract.mint(bob(), token("1".to_string()), secret(), None, None, None);
+        let action_id = contract.transfer_maze(alice(), "1".to_string(), None);
+        contract.execute_admin_action(action_id);
+        let action_id = contract.burn("1".to_string(), None);
+        contract.execute_admin_action(action_id);
+
+        let logs: Vec<String> = get_logs()
+            .into_iter()
+            .filter(|log| log.contains(r#""standard":"nep171""#))
+            .collect();
+        assert_eq!(
+            logs,
+            vec![
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.1.0","event":"nft_mint","data":[{"owner_id":"bob.near","token_ids":["1"]}]}"#,
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.1.0","event":"nft_transfer","data":[{"authorized_id":"owner.near","old_owner_id":"bob.near","new_owner_id":"alice.near","token_ids":["1"]}]}"#,
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.1.0","event":"nft_burn","data":[{"authorized_id":"owner.near","owner_id":"alice.near","token_ids":["1"]}]}"#,
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Only token owner can release hints.")]
+    fn test_release_hint_requires_owner() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(bob(), token("1".to_string()), secret(), None, None, None);
+        contract.release_hint("1".to_string(), "Starts with a".to_string());
+    }
+
+    #[test]
+    fn test_mazes_gameplay_events() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(bob(), token("1".to_string()), secret(), None, None, None);
+        assert_eq!(
+            get_logs()[1],
+            r#"EVENT_JSON:{"standard":"mazes","version":"1.0.0","event":"maze_created","data":[{"creator_id":"bob.near","token_id":"1"}]}"#
+        );
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .build());
+
+        contract.set_account_description("1".to_string(), "c3d4".to_string());
+        assert_eq!(
+            get_logs(),
+            vec![
+                r#"EVENT_JSON:{"standard":"mazes","version":"1.0.0","event":"secret_rotated","data":[{"account_id":"bob.near","token_id":"1"}]}"#
+            ]
+        );
+
+        contract.release_hint("1".to_string(), "Starts with c".to_string());
+        assert_eq!(
+            get_logs()[1],
+            r#"EVENT_JSON:{"standard":"mazes","version":"1.0.0","event":"hint_released","data":[{"token_id":"1","hint":"Starts with c"}]}"#
+        );
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        assert!(!contract.user_burn("1".to_string(), &secret(), None));
+        assert_eq!(
+            get_logs(),
+            vec![
+                r#"EVENT_JSON:{"standard":"mazes","version":"1.0.0","event":"guess_failed","data":[{"account_id":"alice.near","token_id":"1"}]}"#
+            ]
+        );
+        assert!(contract.nft_token("1".to_string()).is_some());
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &"c3d4".to_string(), None));
+        assert_eq!(
+            get_logs().last().unwrap(),
+            r#"EVENT_JSON:{"standard":"mazes","version":"1.0.0","event":"maze_solved","data":[{"solver_id":"alice.near","owner_id":"bob.near","token_id":"1"}]}"#
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Only token owner can change the secret.")]
+    fn test_set_secret_by_non_owner() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(bob(), token("1".to_string()), secret(), None, None, None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        contract.set_account_description("1".to_string(), "c3d4".to_string());
+    }
+
+    #[test]
+    fn test_royalty_payout() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        let royalty = HashMap::from([(bob(), 1_000), (owner(), 500)]);
+        contract.mint(
+            bob(),
+            token("1".to_string()),
+            secret(),
+            None,
+            Some(royalty),
+            None,
+        );
+
+        // ------------------------------ Bob 在市场上把 NFT 卖给 Alice ------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .attached_deposit(ONE_YOCTO)
+            .build());
+
+        let payout = contract.nft_transfer_payout(
+            alice(),
+            "1".to_string(),
+            None,
+            None,
+            U128(10_000),
+            Some(10),
+        );
+
+        assert_eq!(
+            payout.payout,
+            HashMap::from([(bob(), U128(9_500)), (owner(), U128(500))])
+        );
+        assert_eq!(
+            contract.nft_token("1".to_string()).unwrap().token.owner_id,
+            alice()
+        );
+        assert_eq!(
+            contract
+                .nft_payout("1".to_string(), U128(10_000), None)
+                .payout,
+            HashMap::from([
+                (alice(), U128(8_500)),
+                (bob(), U128(1_000)),
+                (owner(), U128(500))
+            ])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Total royalty cannot exceed 5000 basis points.")]
+    fn test_royalty_cap() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        let royalty = HashMap::from([(bob(), 3_000), (alice(), 3_000)]);
+        contract.mint(
+            bob(),
+            token("1".to_string()),
+            secret(),
+            None,
+            Some(royalty),
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Balance is too large to calculate royalties.")]
+    fn test_payout_balance_overflow() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        let royalty = HashMap::from([(bob(), 1_000)]);
+        contract.mint(
+            alice(),
+            token("1".to_string()),
+            secret(),
+            None,
+            Some(royalty),
+            None,
+        );
+        contract.nft_payout("1".to_string(), U128(u128::MAX), None);
+    }
+
+    #[test]
+    fn test_update_contract_metadata() {
+        let mut contract = Contract::init(owner(), None);
+        assert_eq!(contract.nft_metadata().name, "NFT Mazes Contract");
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        let mut metadata = contract.nft_metadata();
+        metadata.name = "Mazes".to_string();
+        metadata.icon = Some("data:image/svg+xml,%3Csvg%3E%3C/svg%3E".to_string());
+        contract.set_contract_metadata(metadata, None);
+
+        assert_eq!(contract.nft_metadata().name, "Mazes");
+        assert_eq!(
+            get_logs(),
+            vec![
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.1.0","event":"contract_metadata_update","data":[{}]}"#
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Icon must be a data URI.")]
+    fn test_update_contract_metadata_with_external_icon() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        let mut metadata = contract.nft_metadata();
+        metadata.icon = Some("https://example.com/icon.png".to_string());
+        contract.set_contract_metadata(metadata, None);
+    }
+
+    #[test]
+    fn test_migrate_from_v0() {
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(contract_account())
+            .predecessor_account_id(contract_account())
+            .build());
+
+        // ------------------------------ 写入未记录版本的初始布局 ----------------------------------
+
+        let mut tokens = NonFungibleToken::new(
+            StorageKey::NonFungibleToken,
+            owner(),
+            Some(StorageKey::TokenMetadata),
+            Some(StorageKey::Enumeration),
+            Some(StorageKey::Approval),
+        );
+        tokens.internal_mint_with_refund(
+            "1".to_string(),
+            bob(),
+            Some(token("1".to_string())),
+            None,
+        );
+        let mut secrets = LookupMap::new(StorageKey::Secret);
+        secrets.insert("1".to_string(), secret());
+        env::state_write(&ContractV0 {
+            owner_id: owner(),
+            tokens,
+            unique_id: 1,
+            secret: secrets,
+        });
+
+        let mut contract = Contract::migrate();
+
+        assert_eq!(contract.view_state_version(), CURRENT_STATE_VERSION);
+        assert_eq!(
+            contract.nft_token("1".to_string()).unwrap().token.owner_id,
+            bob()
+        );
+        assert_eq!(contract.view_stats().minted, 1);
+        assert_eq!(contract.view_stats().burned, 0);
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough gas for migrate.")]
+    fn test_upgrade_without_gas_for_migrate() {
+        let contract = Contract::init(owner(), None);
+
+        let mut context = VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .prepaid_gas(Gas(5_000_000_000_000))
+            .build();
+        context.input = b"code".to_vec();
+        testing_env!(context);
+
+        contract.upgrade();
+    }
+
+    #[test]
+    fn test_two_step_ownership_transfer() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.propose_owner(Some(alice()));
+        assert_eq!(contract.view_owner(), owner());
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        contract.accept_owner();
+        assert_eq!(contract.view_owner(), alice());
+        assert_eq!(contract.view_pending_owner(), None);
+        assert_eq!(
+            get_logs(),
+            vec![
+                r#"EVENT_JSON:{"standard":"mazes","version":"1.0.0","event":"owner_transferred","data":[{"old_owner_id":"owner.near","new_owner_id":"alice.near"}]}"#
+            ]
+        );
+    }
+
+    #[test]
+    fn test_moderator_role_can_burn() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(bob(), token("1".to_string()), secret(), None, None, None);
+        contract.grant_role(alice(), Role::Moderator);
+        assert_eq!(contract.view_roles(alice()), vec![Role::Moderator]);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        let action_id = contract.burn("1".to_string(), None);
+        execute_after_timelock(&mut contract, alice(), action_id);
+        assert!(contract.nft_token("1".to_string()).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only contract owner or Moderator can call this method.")]
+    fn test_burn_without_role() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(bob(), token("1".to_string()), secret(), None, None, None);
+        contract.grant_role(alice(), Role::Moderator);
+        contract.revoke_role(alice(), Role::Moderator);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        contract.burn("1".to_string(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Transfer is paused.")]
+    fn test_paused_transfer() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(bob(), token("1".to_string()), secret(), None, None, None);
+        contract.grant_role(alice(), Role::Pauser);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        contract.set_paused(PauseFeature::Transfer, true);
+        assert!(contract.view_pause_state().transfer);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .attached_deposit(ONE_YOCTO)
+            .build());
+
+        contract.nft_transfer(alice(), "1".to_string(), None, None);
+    }
+
+    #[test]
+    fn test_unpause_guess() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(bob(), token("1".to_string()), secret(), None, None, None);
+        contract.set_paused(PauseFeature::Guess, true);
+        contract.set_paused(PauseFeature::Guess, false);
+        assert_eq!(
+            get_logs().last().unwrap(),
+            r#"EVENT_JSON:{"standard":"mazes","version":"1.0.0","event":"pause_changed","data":[{"account_id":"owner.near","feature":"guess","paused":false}]}"#
+        );
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+    }
+
+    #[test]
+    fn test_holder_objects_to_admin_burn() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(bob(), token("1".to_string()), secret(), None, None, None);
+        let action_id = contract.burn("1".to_string(), None);
+
+        // ---------------------------------- Bob 反对销毁自己的 NFT ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .build());
+
+        contract.object_admin_action(action_id);
+
+        let action = contract.view_admin_action(action_id).unwrap();
+        assert_eq!(
+            action.status,
+            AdminActionStatus::Cancelled {
+                account_id: bob(),
+                timestamp: U64(0),
+                objection: true,
+            }
+        );
+        assert_eq!(contract.view_admin_actions(None, None), vec![action]);
+        assert!(contract.nft_token("1".to_string()).is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "The timelock has not expired yet.")]
+    fn test_admin_action_before_timelock() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(bob(), token("1".to_string()), secret(), None, None, None);
+        let action_id = contract.transfer_maze(alice(), "1".to_string(), None);
+        contract.execute_admin_action(action_id);
+    }
+
+    #[test]
+    fn test_wrapped_nft_goes_to_solver() {
+        let mut contract = Contract::init(owner(), None);
+        wrap_external_nft(&mut contract);
+
+        assert_eq!(
+            contract.nft_token("1".to_string()).unwrap().token.owner_id,
+            alice()
+        );
+        let wrapped = contract.view_wrapped_nft("1".to_string()).unwrap();
+        assert_eq!(wrapped.nft_contract_id, nft_contract());
+        assert_eq!(wrapped.nft_token_id, "42");
+        assert_eq!(wrapped.creator_id, alice());
+        assert_eq!(wrapped.receiver_id, None);
+        // 只保存 secret 的承诺, 查询结果中不包含 salt
+        assert!(!contract.secret.contains_key(&"1".to_string()));
+        let maze = contract.view_maze("1".to_string(), None).unwrap();
+        assert_eq!(maze.solve_mode, SolveMode::Commitment);
+        assert!(!serde_json::to_string(&maze).unwrap().contains("salt"));
+
+        // ---------------------------------- Bob 猜中后获得外部 NFT ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(contract_account())
+            .predecessor_account_id(bob())
+            .build());
+
+        assert!(!contract.user_burn("1".to_string(), &"salta1b2".to_string(), None));
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        assert_eq!(
+            contract
+                .view_wrapped_nft("1".to_string())
+                .unwrap()
+                .receiver_id,
+            Some(bob())
+        );
+
+        // 外部转移失败时保留托管记录, 由 Bob 重新领取
+        testing_env!(
+            VMContextBuilder::new()
+                .current_account_id(contract_account())
+                .predecessor_account_id(contract_account())
+                .build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            HashMap::default(),
+            vec![PromiseResult::Failed],
+        );
+
+        assert!(!contract.on_wrapped_nft_transfer("1".to_string()));
+        assert!(contract.view_wrapped_nft("1".to_string()).is_some());
+
+        testing_env!(
+            VMContextBuilder::new()
+                .current_account_id(contract_account())
+                .predecessor_account_id(contract_account())
+                .build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            HashMap::default(),
+            vec![PromiseResult::Successful(vec![])],
+        );
+
+        assert!(contract.on_wrapped_nft_transfer("1".to_string()));
+        assert!(contract.view_wrapped_nft("1".to_string()).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot change the secret of a wrapped maze.")]
+    fn test_wrapped_maze_secret_cannot_change() {
+        let mut contract = Contract::init(owner(), None);
+        wrap_external_nft(&mut contract);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        contract.set_account_description("1".to_string(), "c3d4".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "The maze has not expired yet.")]
+    fn test_unwrap_before_expiry() {
+        let mut contract = Contract::init(owner(), None);
+        wrap_external_nft(&mut contract);
+
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(contract_account())
+            .predecessor_account_id(alice())
+            .build());
+
+        contract.unwrap_maze("1".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Guess is paused.")]
+    fn test_paused_unwrap() {
+        let mut contract = Contract::init(owner(), None);
+        wrap_external_nft(&mut contract);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.set_paused(PauseFeature::Guess, true);
+
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(contract_account())
+            .predecessor_account_id(alice())
+            .block_timestamp(u64::MAX)
+            .build());
+
+        contract.unwrap_maze("1".to_string());
+    }
+
+    #[test]
+    fn test_ft_bounty_paid_to_solver() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.add_bounty_token(ft_contract(), U128(1));
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+
+        fund_bounty(&mut contract, alice(), "1", 100);
+        fund_bounty(&mut contract, alice(), "1", 20);
+        fund_bounty(&mut contract, owner(), "1", 30);
+
+        assert_eq!(
+            contract.view_bounty("1".to_string()),
+            HashMap::from([(ft_contract(), U128(150))])
+        );
+        assert_eq!(contract.view_bounty_contributions("1".to_string()).len(), 2);
+
+        // ---------------------------------- Bob 猜中后获得全部赏金 ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(contract_account())
+            .predecessor_account_id(bob())
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        assert!(contract.view_bounty("1".to_string()).is_empty());
+        assert!(get_logs()
+            .iter()
+            .any(|log| log.contains(r#""event":"bounty_paid""#)));
+
+        // 转账失败时记为 Bob 待领取
+        testing_env!(
+            VMContextBuilder::new()
+                .current_account_id(contract_account())
+                .predecessor_account_id(contract_account())
+                .build(),
+            VMConfig::test(),
+            RuntimeFeesConfig::test(),
+            HashMap::default(),
+            vec![PromiseResult::Failed],
+        );
+
+        assert!(!contract.on_bounty_transfer(bob(), ft_contract(), U128(150)));
+        assert_eq!(
+            contract.view_bounty_claims(bob()),
+            HashMap::from([(ft_contract(), U128(150))])
+        );
+    }
+
+    #[test]
+    fn test_self_solve_refunds_bounty() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.add_bounty_token(ft_contract(), U128(1));
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        fund_bounty(&mut contract, bob(), "1", 500);
+
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(contract_account())
+            .predecessor_account_id(alice())
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        assert!(contract.view_bounty("1".to_string()).is_empty());
+        assert!(!get_logs()
+            .iter()
+            .any(|log| log.contains(r#""event":"bounty_paid""#)));
+        assert!(get_logs().iter().any(|log| log.contains(
+            r#""event":"bounty_refunded","data":[{"receiver_id":"bob.near","token_id":"1","currency":"ft.near","amount":"500"}]"#
+        )));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot change the secret of a maze with a bounty.")]
+    fn test_bounty_blocks_secret_rotation() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.add_bounty_token(ft_contract(), U128(1));
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        fund_bounty(&mut contract, bob(), "1", 500);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        contract.set_account_description("1".to_string(), "c3d4".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Bounty amount cannot be less than 100.")]
+    fn test_bounty_below_minimum() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.add_bounty_token(ft_contract(), U128(100));
+        assert_eq!(
+            contract.view_bounty_tokens(),
+            HashMap::from([(ft_contract(), U128(100))])
+        );
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        fund_bounty(&mut contract, bob(), "1", 100);
+        fund_bounty(&mut contract, alice(), "1", 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Token is not whitelisted for bounties.")]
+    fn test_bounty_token_not_whitelisted() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        fund_bounty(&mut contract, bob(), "1", 100);
+    }
+
+    // 在 MAZE 合约中注册存储, 未注册的账户不会获得奖励
+    fn register_reward_account(contract: &mut Contract, account_id: AccountId) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(account_id.clone())
+            .attached_deposit(contract.storage_balance_bounds().min.0)
+            .build());
+
+        contract.storage_deposit(Some(account_id), None);
+    }
+
+    #[test]
+    fn test_solve_mints_maze_rewards() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(
+            alice(),
+            token("1".to_string()),
+            secret(),
+            None,
+            None,
+            Some(3),
+        );
+        assert_eq!(contract.ft_metadata().symbol, "MAZE");
+        register_reward_account(&mut contract, alice());
+        register_reward_account(&mut contract, bob());
+
+        // ---------------------------------- 两天后 Bob 解开迷宫 ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .block_timestamp(2 * 24 * 60 * 60 * 1_000_000_000 + 1)
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        assert_eq!(
+            contract.ft_balance_of(bob()),
+            U128(3 * DEFAULT_SOLVER_REWARD)
+        );
+        assert_eq!(
+            contract.ft_balance_of(alice()),
+            U128(2 * DEFAULT_CREATOR_DAILY_REWARD)
+        );
+        assert_eq!(
+            contract.ft_total_supply(),
+            U128(3 * DEFAULT_SOLVER_REWARD + 2 * DEFAULT_CREATOR_DAILY_REWARD)
+        );
+        assert!(get_logs()[0].contains(r#""event":"ft_mint""#));
+    }
+
+    #[test]
+    fn test_claim_creator_reward() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        register_reward_account(&mut contract, alice());
+
+        // ---------------------------------- 迷宫存活两天后领取 ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .block_timestamp(2 * 24 * 60 * 60 * 1_000_000_000)
+            .build());
+
+        assert_eq!(
+            contract.claim_creator_reward("1".to_string()),
+            U128(2 * DEFAULT_CREATOR_DAILY_REWARD)
+        );
+        assert_eq!(contract.claim_creator_reward("1".to_string()), U128(0));
+
+        // ---------------------------------- 第三天解开时只发放未领取的一天 ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .block_timestamp(3 * 24 * 60 * 60 * 1_000_000_000)
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        assert_eq!(
+            contract.ft_balance_of(alice()),
+            U128(3 * DEFAULT_CREATOR_DAILY_REWARD)
+        );
+    }
+
+    #[test]
+    fn test_pre_upgrade_maze_skips_age_rewards() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        // 模拟升级前 mint 的迷宫没有游戏状态
+        contract.mazes.remove(&"1".to_string());
+        contract.rate_maze("1".to_string(), 1);
+        register_reward_account(&mut contract, alice());
+        register_reward_account(&mut contract, bob());
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .block_index(1_000)
+            .block_timestamp(400 * 24 * 60 * 60 * 1_000_000_000)
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        assert_eq!(contract.ft_balance_of(bob()), U128(DEFAULT_SOLVER_REWARD));
+        assert_eq!(contract.ft_balance_of(alice()), U128(0));
+        assert!(contract.view_achievements(alice()).is_empty());
+        let ids: Vec<String> = contract
+            .view_achievements(bob())
+            .into_iter()
+            .map(|achievement| achievement.id)
+            .collect();
+        assert_eq!(ids, vec!["first_solve"]);
+    }
+
+    #[test]
+    fn test_unrated_maze_earns_no_rewards() {
+        let mut contract = Contract::init(owner(), None);
+        register_reward_account(&mut contract, alice());
+        register_reward_account(&mut contract, bob());
+
+        // ---------------------------------- 普通账户 mint 的迷宫未评定难度 ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        contract.mint(alice(), token("2".to_string()), secret(), None, None, None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        assert_eq!(contract.ft_total_supply(), U128(0));
+
+        // ---------------------------------- Minter 评定后才发放奖励 ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.rate_maze("2".to_string(), 2);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .build());
+
+        assert!(contract.user_burn("2".to_string(), &secret(), None));
+        assert_eq!(
+            contract.ft_balance_of(bob()),
+            U128(2 * DEFAULT_SOLVER_REWARD)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Only contract owner or Minter can set difficulty.")]
+    fn test_mint_difficulty_requires_minter() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        contract.mint(
+            alice(),
+            token("1".to_string()),
+            secret(),
+            None,
+            None,
+            Some(5),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Difficulty must be between 1 and 5.")]
+    fn test_mint_invalid_difficulty() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(
+            alice(),
+            token("1".to_string()),
+            secret(),
+            None,
+            None,
+            Some(6),
+        );
+    }
+
+    #[test]
+    fn test_solve_mints_trophy() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .block_index(42)
+            .build());
+
+        assert!(!contract.user_burn("1".to_string(), &"c3d4".to_string(), None));
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+
+        let trophy = contract.sbt_token("trophy-1".to_string()).unwrap();
+        assert_eq!(trophy.owner_id, bob());
+        assert_eq!(trophy.maze_id, "1");
+        assert_eq!(trophy.creator_id, Some(alice()));
+        assert_eq!(trophy.block_height, U64(42));
+        assert_eq!(trophy.attempts, 2);
+        assert_eq!(contract.sbt_supply_by_owner(bob()), 1);
+        assert_eq!(
+            contract.sbt_tokens_by_owner(bob(), None, None),
+            vec![trophy]
+        );
+        assert!(contract.sbt_tokens_by_owner(alice(), None, None).is_empty());
+    }
+
+    #[test]
+    fn test_self_solve_earns_no_trophy() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        assert_eq!(contract.sbt_supply_by_owner(alice()), 0);
+        assert!(contract.view_achievements(alice()).is_empty());
+        let stats = contract.view_player_stats(alice());
+        assert_eq!(stats.guesses, 1);
+        assert_eq!(stats.solves, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Trophies are soulbound and cannot be transferred or approved.")]
+    fn test_trophy_cannot_be_transferred() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .attached_deposit(ONE_YOCTO)
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        contract.nft_transfer(alice(), "trophy-1".to_string(), None, None);
+    }
+
+    #[test]
+    fn test_solve_awards_achievements() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .block_index(10)
+            .build());
+
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        contract.mint(alice(), token("2".to_string()), secret(), None, None, None);
+
+        // ---------------------------------- 第一个迷宫在 100 个区块内解开 ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .block_index(110)
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        let ids: Vec<String> = contract
+            .view_achievements(bob())
+            .into_iter()
+            .map(|achievement| achievement.id)
+            .collect();
+        assert_eq!(ids, vec!["first_solve", "speed_solver"]);
+
+        // ---------------------------------- 第二个迷宫存活 30 天后解开 ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .block_index(1_000)
+            .block_timestamp(30 * 24 * 60 * 60 * 1_000_000_000)
+            .build());
+
+        assert!(!contract.user_burn("2".to_string(), &"c3d4".to_string(), None));
+        assert!(contract.user_burn("2".to_string(), &secret(), None));
+        assert_eq!(contract.view_achievements(bob()).len(), 2);
+        assert_eq!(contract.view_achievements(alice())[0].id, "survivor");
+
+        let stats = contract.view_player_stats(bob());
+        assert_eq!(stats.guesses, 3);
+        assert_eq!(stats.failed_guesses, 1);
+        assert_eq!(stats.solves, 2);
+        assert_eq!(contract.view_player_stats(alice()).mazes_created, 2);
+    }
+
+    #[test]
+    fn test_register_achievement() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.register_achievement(AchievementDefinition {
+            id: "architect".to_string(),
+            name: "Architect".to_string(),
+            description: None,
+            condition: AchievementCondition::MazesCreated { count: 2 },
+        });
+
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        assert!(contract.view_achievements(alice()).is_empty());
+
+        contract.mint(alice(), token("2".to_string()), secret(), None, None, None);
+        assert_eq!(contract.view_achievements(alice())[0].id, "architect");
+        assert!(get_logs()
+            .iter()
+            .any(|log| log.contains(r#""event":"achievement_awarded""#)));
+    }
+
+    fn mint_request(account_id: AccountId, token_id: &str) -> MintRequest {
+        MintRequest {
+            account_id,
+            metadata: token(token_id.to_string()),
+            secret: secret(),
+            royalty: None,
+            difficulty: None,
+        }
+    }
+
+    #[test]
+    fn test_batch_mint() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.grant_role(alice(), Role::Minter);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        let token_ids = contract.nft_batch_mint(vec![
+            mint_request(bob(), "1"),
+            mint_request(alice(), "2"),
+            mint_request(bob(), "3"),
+        ]);
+        assert_eq!(token_ids, vec!["1", "2", "3"]);
+        assert_eq!(contract.nft_supply_for_owner(bob()), U128(2));
+
+        let mint_logs: Vec<String> = get_logs()
+            .into_iter()
+            .filter(|log| log.contains(r#""event":"nft_mint""#))
+            .collect();
+        assert_eq!(
+            mint_logs,
+            vec![
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.1.0","event":"nft_mint","data":[{"owner_id":"bob.near","token_ids":["1","3"]},{"owner_id":"alice.near","token_ids":["2"]}]}"#
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Difficulty must be between 1 and 5.")]
+    fn test_batch_mint_validates_before_writing() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        let mut invalid = mint_request(alice(), "2");
+        invalid.difficulty = Some(0);
+        contract.nft_batch_mint(vec![mint_request(bob(), "1"), invalid]);
+    }
+
+    #[test]
+    #[should_panic(expected = "This image appears more than once in the batch.")]
+    fn test_batch_mint_rejects_duplicate_media() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        let mut first = mint_request(bob(), "1");
+        first.metadata = token_with_media("1".to_string(), b"maze");
+        let mut second = mint_request(bob(), "2");
+        second.metadata = token_with_media("2".to_string(), b"maze");
+        contract.nft_batch_mint(vec![first, second]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the original creator can mint this image again.")]
+    fn test_batch_mint_duplicate_media_creator_only() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.set_duplicate_policy(DuplicatePolicy::CreatorOnly);
+        let requests = |accounts: [AccountId; 2], content: &[u8]| {
+            accounts
+                .into_iter()
+                .enumerate()
+                .map(|(index, account_id)| {
+                    let token_id = (index + 1).to_string();
+                    let mut request = mint_request(account_id, &token_id);
+                    request.metadata = token_with_media(token_id, content);
+                    request
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            contract
+                .nft_batch_mint(requests([bob(), bob()], b"maze"))
+                .len(),
+            2
+        );
+        contract.nft_batch_mint(requests([alice(), bob()], b"other"));
+    }
+
+    #[test]
+    fn test_batch_transfer_and_burn() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.nft_batch_mint(vec![
+            mint_request(bob(), "1"),
+            mint_request(bob(), "2"),
+            mint_request(bob(), "3"),
+        ]);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .attached_deposit(ONE_YOCTO)
+            .build());
+
+        contract.batch_transfer(alice(), vec!["1".to_string(), "2".to_string()]);
+        assert_eq!(contract.nft_supply_for_owner(alice()), U128(2));
+        assert_eq!(
+            get_logs(),
+            vec![
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.1.0","event":"nft_transfer","data":[{"old_owner_id":"bob.near","new_owner_id":"alice.near","token_ids":["1","2"]}]}"#
+            ]
+        );
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(ONE_YOCTO)
+            .build());
+
+        assert!(contract
+            .batch_burn(vec!["1".to_string(), "2".to_string()], None)
+            .is_empty());
+        assert_eq!(contract.nft_supply_for_owner(alice()), U128(0));
+        assert_eq!(
+            get_logs(),
+            vec![
+                r#"EVENT_JSON:{"standard":"nep171","version":"1.1.0","event":"nft_burn","data":[{"owner_id":"alice.near","token_ids":["1","2"]}]}"#
+            ]
+        );
+
+        // ---------------------------------- 合约所有者销毁 Bob 的 NFT 需要排队 ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .attached_deposit(ONE_YOCTO)
+            .build());
+
+        let action_ids = contract.batch_burn(vec!["3".to_string()], None);
+        assert_eq!(action_ids, vec![0]);
+        assert!(contract.nft_token("3".to_string()).is_some());
+
+        execute_after_timelock(&mut contract, owner(), 0);
+        assert!(contract.nft_token("3".to_string()).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Wrapped maze 1 has not expired yet.")]
+    fn test_batch_burn_wrapped_before_expiry() {
+        let mut contract = Contract::init(owner(), None);
+        wrap_external_nft(&mut contract);
+
+        testing_env!(VMContextBuilder::new()
+            .current_account_id(contract_account())
+            .predecessor_account_id(alice())
+            .attached_deposit(ONE_YOCTO)
+            .build());
+
+        contract.batch_burn(vec!["1".to_string()], None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Sender does not own token 2.")]
+    fn test_batch_transfer_fails_atomically() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.nft_batch_mint(vec![mint_request(bob(), "1"), mint_request(alice(), "2")]);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .attached_deposit(ONE_YOCTO)
+            .build());
+
+        contract.batch_transfer(owner(), vec!["1".to_string(), "2".to_string()]);
+    }
+
+    #[test]
+    fn test_creator_tracked_after_transfer() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        contract.mint(alice(), token("2".to_string()), secret(), None, None, None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(ONE_YOCTO)
+            .build());
+
+        contract.nft_transfer(bob(), "1".to_string(), None, None);
+
+        let token = contract.nft_token("1".to_string()).unwrap();
+        assert_eq!(token.token.owner_id, bob());
+        assert_eq!(token.creator_id, Some(alice()));
+        assert!(near_sdk::serde_json::to_string(&token)
+            .unwrap()
+            .contains(r#""creator_id":"alice.near""#));
+
+        let mazes = contract.view_mazes_by_creator(alice(), None, None);
+        assert_eq!(mazes.len(), 2);
+        assert_eq!(
+            contract
+                .view_mazes_by_creator(alice(), Some(U128(1)), Some(1))
+                .len(),
+            1
+        );
+        assert!(contract.view_mazes_by_creator(bob(), None, None).is_empty());
+
+        // ---------------------------------- 解开后从创建者的集合中移除 ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        assert_eq!(contract.view_mazes_by_creator_supply(alice()), U128(1));
+        assert_eq!(contract.view_creator("1".to_string()), None);
+    }
+
+    #[test]
+    fn test_paginate_mazes_with_cursor() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        for id in 1..=5 {
+            contract.mint(alice(), token(id.to_string()), secret(), None, None, None);
+        }
+        contract.mint(bob(), token("6".to_string()), secret(), None, None, None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(ONE_YOCTO)
+            .build());
+
+        contract.nft_transfer(bob(), "3".to_string(), None, None);
+
+        let ids = |page: &Page<MazeToken>| {
+            page.items
+                .iter()
+                .map(|maze| maze.token.token_id.clone())
+                .collect::<Vec<_>>()
+        };
+
+        // ---------------------------------- 按 cursor 翻页 ----------------------------------
+
+        let first = contract.view_nft_by_id(alice(), None, Some(2), None, None);
+        assert_eq!(ids(&first), vec!["1", "2"]);
+        let second = contract.view_nft_by_id(alice(), None, Some(2), first.next_cursor, None);
+        assert_eq!(ids(&second), vec!["4", "5"]);
+        assert_eq!(second.next_cursor, None);
+
+        let newest = contract.view_nft_by_id(bob(), None, None, None, Some(SortOrder::NewestFirst));
+        assert_eq!(ids(&newest), vec!["6", "3"]);
+
+        // ---------------------------------- 未知账户返回空页 ----------------------------------
+
+        let empty = contract.view_nft_by_id(owner(), None, None, None, None);
+        assert!(empty.items.is_empty());
+        assert_eq!(empty.next_cursor, None);
+
+        // ---------------------------------- 全部 NFT 跳过已销毁的 id ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .build());
+        assert!(contract.user_burn("4".to_string(), &secret(), None));
+
+        let page = contract.view_all_nft(None, Some(3), None, Some(SortOrder::NewestFirst));
+        assert_eq!(ids(&page), vec!["6", "5", "3"]);
+        let page = contract.view_all_nft(
+            None,
+            Some(3),
+            page.next_cursor,
+            Some(SortOrder::NewestFirst),
+        );
+        assert_eq!(ids(&page), vec!["2", "1"]);
+        assert_eq!(page.next_cursor, None);
+        assert_eq!(
+            ids(&contract.view_all_nft(Some(U128(1)), Some(1), None, None)),
+            vec!["2"]
+        );
+        assert_eq!(
+            contract
+                .view_all_nft(None, Some(500), None, None)
+                .items
+                .len(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_paginate_skip_beyond_scan_window() {
+        let mut contract = Contract::init(owner(), None);
+        // 前 `MAX_PAGE_SCAN` 个 id 都已不存在
+        contract.unique_id = MAX_PAGE_SCAN;
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        for id in 1..=10 {
+            let account_id = if id % 2 == 0 { alice() } else { bob() };
+            contract.mint(
+                account_id,
+                token(id.to_string()),
+                secret(),
+                None,
+                None,
+                None,
+            );
+        }
+        let ids = |page: Page<MazeToken>| {
+            page.items
+                .into_iter()
+                .map(|maze| maze.token.token_id)
+                .collect::<Vec<_>>()
+        };
+
+        // ---------------------------------- 未跳过的数量保存在游标中 ----------------------------------
+
+        let page = contract.view_all_nft(Some(U128(5)), Some(2), None, None);
+        assert!(page.items.is_empty());
+        assert_eq!(
+            ids(contract.view_all_nft(None, Some(2), page.next_cursor, None)),
+            vec!["1006", "1007"]
+        );
+
+        // ---------------------------------- 账户的 NFT 按 id 排序 ----------------------------------
+
+        assert_eq!(
+            ids(contract.view_nft_by_id(alice(), Some(U128(3)), Some(2), None, None)),
+            vec!["1008", "1010"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid cursor.")]
+    fn test_paginate_invalid_cursor() {
+        let contract = Contract::init(owner(), None);
+        contract.view_all_nft(None, None, Some("not a cursor".to_string()), None);
+    }
+
+    #[test]
+    fn test_maze_summaries() {
+        let mut contract = Contract::init(owner(), None);
+        wrap_external_nft(&mut contract);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.add_bounty_token(ft_contract(), U128(1));
+        contract.mint(bob(), token("2".to_string()), secret(), None, None, None);
+        fund_bounty(&mut contract, alice(), "2", 100);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        assert!(!contract.user_burn("2".to_string(), &"wrong".to_string(), None));
+
+        let page = contract.view_maze_summaries(None, None, None, None);
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.next_cursor, None);
+        assert!(!serde_json::to_string(&page)
+            .unwrap()
+            .contains(r#""media":"#));
+
+        let wrapped = &page.items[0];
+        assert_eq!(wrapped.token_id, "1");
+        assert_eq!(wrapped.owner_id, alice());
+        assert_eq!(wrapped.status, MazeStatus::Open);
+        assert!(wrapped.expires_at.is_some());
+
+        let summaries = contract.view_maze_summaries_by_owner(bob(), None, None, None, None);
+        let summary = &summaries.items[0];
+        assert_eq!(summary.token_id, "2");
+        assert_eq!(summary.creator_id, Some(bob()));
+        assert_eq!(summary.title, Some("HelloNFT #2".to_string()));
+        assert_eq!(summary.bounty, HashMap::from([(ft_contract(), U128(100))]));
+        assert_eq!(summary.attempts, 1);
+        assert_eq!(summary.expires_at, None);
+
+        // ---------------------------------- 过期后状态变为 expired ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .block_timestamp(wrapped.expires_at.unwrap().0)
+            .build());
+
+        let page = contract.view_maze_summaries_by_owner(alice(), None, None, None, None);
+        assert_eq!(page.items[0].status, MazeStatus::Expired);
+    }
+
+    #[test]
+    fn test_view_mazes_by_index() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.add_bounty_token(ft_contract(), U128(1));
+        for (id, difficulty) in [(1, 1), (2, 3), (3, 1), (4, 2)] {
+            contract.mint(
+                alice(),
+                token(id.to_string()),
+                secret(),
+                None,
+                None,
+                Some(difficulty),
+            );
+        }
+        fund_bounty(&mut contract, bob(), "2", 50);
+        fund_bounty(&mut contract, bob(), "3", 100);
+        fund_bounty(&mut contract, bob(), "4", 100);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+
+        let ids = |page: &Page<MazeSummary>| {
+            page.items
+                .iter()
+                .map(|summary| summary.token_id.clone())
+                .collect::<Vec<_>>()
+        };
+        let filter = |difficulty: Option<u8>, min_bounty: Option<u128>| {
+            Some(MazeFilter {
+                status: None,
+                difficulty,
+                bounty_token: Some(ft_contract()),
+                min_bounty: min_bounty.map(U128),
+            })
+        };
+
+        // ---------------------------------- 按 mint 顺序 ----------------------------------
+
+        let page = contract.view_mazes(None, None, None, Some(2), None);
+        assert_eq!(ids(&page), vec!["2", "3"]);
+        let page = contract.view_mazes(None, None, None, Some(2), page.next_cursor);
+        assert_eq!(ids(&page), vec!["4"]);
+        assert_eq!(page.next_cursor, None);
+
+        let page = contract.view_mazes(None, None, Some(SortOrder::NewestFirst), None, None);
+        assert_eq!(ids(&page), vec!["4", "3", "2"]);
+        let page = contract.view_mazes(filter(Some(1), None), None, None, None, None);
+        assert_eq!(ids(&page), vec!["3"]);
+
+        // ---------------------------------- 按赏金排序 ----------------------------------
+
+        let page = contract.view_mazes(
+            filter(None, None),
+            Some(MazeSortBy::Bounty),
+            None,
+            None,
+            None,
+        );
+        assert_eq!(ids(&page), vec!["4", "3", "2"]);
+        let page = contract.view_mazes(
+            filter(None, Some(100)),
+            Some(MazeSortBy::Bounty),
+            None,
+            Some(1),
+            None,
+        );
+        assert_eq!(ids(&page), vec!["4"]);
+        let page = contract.view_mazes(
+            filter(None, Some(100)),
+            Some(MazeSortBy::Bounty),
+            None,
+            Some(1),
+            page.next_cursor,
+        );
+        assert_eq!(ids(&page), vec!["3"]);
+        assert_eq!(page.next_cursor, None);
+
+        fund_bounty(&mut contract, bob(), "2", 150);
+        let page = contract.view_mazes(
+            filter(None, None),
+            Some(MazeSortBy::Bounty),
+            None,
+            None,
+            None,
+        );
+        assert_eq!(ids(&page), vec!["2", "4", "3"]);
+        assert_eq!(page.items[0].bounty[&ft_contract()], U128(200));
+
+        // ---------------------------------- 已解开的迷宫 ----------------------------------
+
+        let solved = Some(MazeFilter {
+            status: Some(MazeStatus::Solved),
+            ..Default::default()
+        });
+        let page = contract.view_mazes(solved, None, None, None, None);
+        assert_eq!(ids(&page), vec!["1"]);
+        assert_eq!(page.items[0].status, MazeStatus::Solved);
+        assert_eq!(page.items[0].attempts, 1);
+    }
+
+    #[test]
+    fn test_solved_mazes_keep_newest() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        contract.mint(alice(), token("2".to_string()), secret(), None, None, None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .build());
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        // 之后解开的迷宫记录已达上限
+        let solved = SolvedMaze {
+            owner_id: alice(),
+            solver_id: bob(),
+            creator_id: None,
+            title: None,
+            attempts: 1,
+            bounty: HashMap::new(),
+        };
+        for id in 1..u64::from(MAX_SOLVED_MAZES) {
+            contract
+                .solved_mazes
+                .insert((1 + (id % 2) as u8, 100 + id), solved.clone());
+        }
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .build());
+
+        assert!(contract.user_burn("2".to_string(), &secret(), None));
+        assert_eq!(contract.solved_mazes.len(), MAX_SOLVED_MAZES);
+        assert_eq!(
+            contract.view_maze("2".to_string(), None).unwrap().solver_id,
+            Some(bob())
+        );
+
+        // 最早的记录被移除后仍然可以查到已解开
+        let maze = contract.view_maze("1".to_string(), None).unwrap();
+        assert_eq!(maze.status, MazeStatus::Solved);
+        assert_eq!(maze.difficulty, 1);
+        assert_eq!(maze.solver_id, None);
+        let solved = Some(MazeFilter {
+            status: Some(MazeStatus::Solved),
+            difficulty: Some(1),
+            ..Default::default()
+        });
+        let page = contract.view_mazes(solved, None, None, Some(1), None);
+        assert_eq!(page.items[0].token_id, "2");
+    }
+
+    #[test]
+    fn test_reindex_mazes() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.add_bounty_token(ft_contract(), U128(1));
+        contract.mint(
+            alice(),
+            token("1".to_string()),
+            secret(),
+            None,
+            None,
+            Some(2),
+        );
+        contract.mint(alice(), token("2".to_string()), secret(), None, None, None);
+        fund_bounty(&mut contract, bob(), "2", 50);
+
+        // 模拟升级前 mint 的迷宫没有索引
+        contract.maze_index.clear();
+        contract.bounty_index.clear();
+        contract.owner_index.clear();
+        let by_bounty = |contract: &Contract| {
+            contract.view_mazes(
+                Some(MazeFilter {
+                    bounty_token: Some(ft_contract()),
+                    ..Default::default()
+                }),
+                Some(MazeSortBy::Bounty),
+                None,
+                None,
+                None,
+            )
+        };
+        assert!(contract
+            .view_mazes(None, None, None, None, None)
+            .items
+            .is_empty());
+        assert!(by_bounty(&contract).items.is_empty());
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        assert_eq!(contract.reindex_mazes(None, Some(1)), 1);
+        assert_eq!(contract.reindex_mazes(Some(U128(1)), None), 1);
+        assert_eq!(contract.reindex_mazes(None, None), 2);
+
+        let page = contract.view_mazes(None, None, None, None, None);
+        assert_eq!(page.items.len(), 2);
+        let page = contract.view_mazes(
+            Some(MazeFilter {
+                difficulty: Some(2),
+                ..Default::default()
+            }),
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(page.items[0].token_id, "1");
+        assert_eq!(by_bounty(&contract).items[0].token_id, "2");
+        let page = contract.view_maze_summaries_by_owner(alice(), None, None, None, None);
+        assert_eq!(page.items.len(), 2);
+    }
+
+    #[test]
+    fn test_contract_stats() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.add_bounty_token(ft_contract(), U128(1));
+        contract.mint(alice(), token("1".to_string()), secret(), None, None, None);
+        contract.mint(alice(), token("2".to_string()), secret(), None, None, None);
+        fund_bounty(&mut contract, bob(), "1", 100);
+        fund_bounty(&mut contract, alice(), "2", 30);
+
+        let stats = contract.view_stats();
+        assert_eq!(stats.minted, 2);
+        assert_eq!(stats.players, 1);
+        assert_eq!(
+            stats.active_bounty,
+            HashMap::from([(ft_contract(), U128(130))])
+        );
+
+        // ---------------------------------- Bob 猜错一次后猜中 ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .build());
+
+        assert!(!contract.user_burn("1".to_string(), &"wrong".to_string(), None));
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+
+        // ---------------------------------- Alice 销毁自己的迷宫, 赏金退还 ----------------------------------
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .attached_deposit(ONE_YOCTO)
+            .build());
+
+        contract.batch_burn(vec!["2".to_string()], None);
+
+        let stats = contract.view_stats();
+        assert_eq!(stats.minted, 2);
+        assert_eq!(stats.burned, 2);
+        assert_eq!(stats.solved, 1);
+        assert_eq!(stats.guesses, 2);
+        assert_eq!(stats.failed_guesses, 1);
+        assert_eq!(stats.players, 2);
+        assert!(stats.active_bounty.is_empty());
+    }
+
+    #[test]
+    fn test_view_maze_hides_secret() {
+        let mut contract = Contract::init(owner(), None);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(owner())
+            .build());
+
+        contract.add_bounty_token(ft_contract(), U128(1));
+        contract.mint(
+            alice(),
+            token("1".to_string()),
+            secret(),
+            None,
+            None,
+            Some(3),
+        );
+        fund_bounty(&mut contract, alice(), "1", 100);
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(alice())
+            .build());
+
+        contract.release_hint("1".to_string(), "Four characters".to_string());
+
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(bob())
+            .build());
+
+        assert!(!contract.user_burn("1".to_string(), &"wrong".to_string(), None));
+        assert!(!contract.user_burn("1".to_string(), &"again".to_string(), None));
+
+        let maze = contract.view_maze("1".to_string(), Some(bob())).unwrap();
+        assert_eq!(maze.token.as_ref().unwrap().owner_id, alice());
+        assert_eq!(maze.creator_id, Some(alice()));
+        assert_eq!(maze.status, MazeStatus::Open);
+        assert_eq!(maze.solve_mode, SolveMode::Secret);
+        assert_eq!(maze.difficulty, 3);
+        assert_eq!(maze.bounty, HashMap::from([(ft_contract(), U128(100))]));
+        assert_eq!(maze.attempts, 2);
+        assert_eq!(maze.viewer_attempts, Some(2));
+        assert_eq!(maze.guess_fee, U128(0));
+        assert_eq!(maze.cooldown_remaining, U64(0));
+        assert_eq!(maze.hints, vec!["Four characters"]);
+        assert_eq!(maze.expires_at, None);
+        assert!(!serde_json::to_string(&maze).unwrap().contains(&secret()));
+
+        let maze = contract.view_maze("1".to_string(), Some(alice())).unwrap();
+        assert_eq!(maze.viewer_attempts, Some(0));
+        assert_eq!(
+            contract
+                .view_maze("1".to_string(), None)
+                .unwrap()
+                .viewer_attempts,
+            None
+        );
+
+        // ---------------------------------- 解开后返回记录的信息 ----------------------------------
+
+        assert!(contract.user_burn("1".to_string(), &secret(), None));
+        let maze = contract.view_maze("1".to_string(), Some(bob())).unwrap();
+        assert!(maze.token.is_none());
+        assert_eq!(maze.creator_id, Some(alice()));
+        assert_eq!(maze.status, MazeStatus::Solved);
+        assert_eq!(maze.solver_id, Some(bob()));
+        assert_eq!(maze.difficulty, 3);
+        assert_eq!(maze.bounty, HashMap::from([(ft_contract(), U128(100))]));
+        assert_eq!(maze.attempts, 3);
+        assert_eq!(maze.viewer_attempts, None);
+        assert!(contract.view_maze("2".to_string(), None).is_none());
+    }
 }
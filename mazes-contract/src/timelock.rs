@@ -0,0 +1,250 @@
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+use crate::*;
+
+// 管理操作默认需要等待 2 天才能执行
+pub const DEFAULT_ADMIN_TIMELOCK: u64 = 2 * 24 * 60 * 60 * 1_000_000_000;
+// 等待时间上限 30 天
+pub const MAX_ADMIN_TIMELOCK: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+// 单次查询的管理操作数量上限
+pub const MAX_ADMIN_ACTIONS_LIMIT: u64 = 100;
+
+/// 需要等待时间锁的管理操作
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AdminActionKind {
+    // 把 NFT 转移给 `receiver_id`
+    Transfer { receiver_id: AccountId },
+    // 销毁 NFT
+    Burn,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum AdminActionStatus {
+    // 等待执行
+    Pending,
+    // 已执行
+    Executed {
+        account_id: AccountId,
+        timestamp: U64,
+    },
+    // 被 NFT 持有者反对 (`objection` 为 `true`) 或被合约所有者取消
+    Cancelled {
+        account_id: AccountId,
+        timestamp: U64,
+        objection: bool,
+    },
+}
+
+/// 管理操作及其审计记录, 所有记录永久保留在链上
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AdminAction {
+    pub id: u64,
+    pub token_id: TokenId,
+    // 发起时 NFT 的持有者, 执行时持有者变化则无法执行
+    pub owner_id: AccountId,
+    pub action: AdminActionKind,
+    pub proposer_id: AccountId,
+    pub memo: Option<String>,
+    pub queued_at: U64,
+    pub executable_at: U64,
+    pub status: AdminActionStatus,
+}
+
+#[near_bindgen]
+impl Contract {
+    // 合约所有者或 Moderator 发起代为转移 NFT, 返回管理操作 id
+    pub fn transfer_maze(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        memo: Option<String>,
+    ) -> u64 {
+        self.internal_queue_admin_action(token_id, AdminActionKind::Transfer { receiver_id }, memo)
+    }
+
+    // 合约所有者或 Moderator 发起销毁 NFT, 返回管理操作 id
+    pub fn burn(&mut self, token_id: TokenId, memo: Option<String>) -> u64 {
+        self.internal_queue_admin_action(token_id, AdminActionKind::Burn, memo)
+    }
+
+    // 等待时间结束后, 合约所有者或 Moderator 执行管理操作
+    pub fn execute_admin_action(&mut self, action_id: u64) {
+        self.assert_role(Role::Moderator);
+        let mut action = self.internal_pending_admin_action(action_id);
+        require!(
+            env::block_timestamp() >= action.executable_at.0,
+            "The timelock has not expired yet."
+        );
+        require!(
+            self.tokens.owner_by_id.get(&action.token_id).as_ref() == Some(&action.owner_id),
+            "The token owner has changed since the action was queued."
+        );
+
+        match &action.action {
+            AdminActionKind::Transfer { receiver_id } => {
+                self.assert_not_paused(PauseFeature::Transfer);
+                self.internal_admin_transfer(&action.token_id, receiver_id, action.memo.clone());
+            }
+            AdminActionKind::Burn => {
+                self.assert_not_paused(PauseFeature::Guess);
+                self.internal_burn(&action.token_id, action.memo.clone());
+                self.internal_refund_escrow(&action.token_id);
+            }
+        }
+
+        action.status = AdminActionStatus::Executed {
+            account_id: env::predecessor_account_id(),
+            timestamp: U64(env::block_timestamp()),
+        };
+        self.internal_finish_admin_action(action);
+    }
+
+    // NFT 持有者反对针对自己 NFT 的管理操作
+    pub fn object_admin_action(&mut self, action_id: u64) {
+        let mut action = self.internal_pending_admin_action(action_id);
+        let account_id = env::predecessor_account_id();
+        require!(
+            self.tokens.owner_by_id.get(&action.token_id).as_ref() == Some(&account_id),
+            "Only the token holder can object to this action."
+        );
+
+        action.status = AdminActionStatus::Cancelled {
+            account_id,
+            timestamp: U64(env::block_timestamp()),
+            objection: true,
+        };
+        self.internal_finish_admin_action(action);
+    }
+
+    // 合约所有者取消管理操作
+    pub fn cancel_admin_action(&mut self, action_id: u64) {
+        self.assert_owner();
+        let mut action = self.internal_pending_admin_action(action_id);
+
+        action.status = AdminActionStatus::Cancelled {
+            account_id: env::predecessor_account_id(),
+            timestamp: U64(env::block_timestamp()),
+            objection: false,
+        };
+        self.internal_finish_admin_action(action);
+    }
+
+    // 合约所有者设置管理操作的等待时间 (纳秒), 只影响之后发起的操作
+    pub fn set_admin_timelock(&mut self, delay: U64) {
+        self.assert_owner();
+        require!(
+            delay.0 <= MAX_ADMIN_TIMELOCK,
+            format!("Timelock cannot exceed {} nanoseconds.", MAX_ADMIN_TIMELOCK)
+        );
+        self.admin_timelock = delay.0;
+    }
+
+    pub fn view_admin_timelock(&self) -> U64 {
+        U64(self.admin_timelock)
+    }
+
+    pub fn view_admin_action(&self, action_id: u64) -> Option<AdminAction> {
+        self.admin_actions.get(action_id)
+    }
+
+    // 按发起顺序查询管理操作
+    pub fn view_admin_actions(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<AdminAction> {
+        let limit = limit
+            .unwrap_or(MAX_ADMIN_ACTIONS_LIMIT)
+            .min(MAX_ADMIN_ACTIONS_LIMIT);
+        let start = u64::try_from(from_index.map_or(0, |index| index.0)).unwrap_or(u64::MAX);
+
+        (start..self.admin_actions.len())
+            .take(limit as usize)
+            .filter_map(|id| self.admin_actions.get(id))
+            .collect()
+    }
+}
+
+impl Contract {
+    pub(crate) fn internal_queue_admin_action(
+        &mut self,
+        token_id: TokenId,
+        action: AdminActionKind,
+        memo: Option<String>,
+    ) -> u64 {
+        self.assert_role(Role::Moderator);
+        let admin_action = self.internal_push_admin_action(token_id, action, memo);
+
+        MazesEventLog::new(MazesEventLogVariant::AdminActionQueued(vec![
+            admin_action.clone()
+        ]))
+        .emit();
+        admin_action.id
+    }
+
+    // 记录待执行的管理操作, 不检查权限也不记录事件
+    pub(crate) fn internal_push_admin_action(
+        &mut self,
+        token_id: TokenId,
+        action: AdminActionKind,
+        memo: Option<String>,
+    ) -> AdminAction {
+        let owner_id = self
+            .tokens
+            .owner_by_id
+            .get(&token_id)
+            .expect("Token not found.");
+        if let AdminActionKind::Transfer { receiver_id } = &action {
+            require!(
+                receiver_id != &owner_id,
+                "Current and next owner must differ."
+            );
+        }
+
+        let now = env::block_timestamp();
+        let admin_action = AdminAction {
+            id: self.admin_actions.len(),
+            token_id,
+            owner_id,
+            action,
+            proposer_id: env::predecessor_account_id(),
+            memo,
+            queued_at: U64(now),
+            executable_at: U64(now + self.admin_timelock),
+            status: AdminActionStatus::Pending,
+        };
+        self.admin_actions.push(&admin_action);
+        admin_action
+    }
+
+    fn internal_pending_admin_action(&self, action_id: u64) -> AdminAction {
+        let action = self
+            .admin_actions
+            .get(action_id)
+            .expect("Admin action not found.");
+        require!(
+            action.status == AdminActionStatus::Pending,
+            "Admin action is not pending."
+        );
+        action
+    }
+
+    fn internal_finish_admin_action(&mut self, action: AdminAction) {
+        self.admin_actions.replace(action.id, &action);
+
+        let event = match action.status {
+            AdminActionStatus::Executed { .. } => MazesEventLogVariant::AdminActionExecuted,
+            _ => MazesEventLogVariant::AdminActionCancelled,
+        };
+        MazesEventLog::new(event(vec![action])).emit();
+    }
+}
@@ -0,0 +1,109 @@
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near_bindgen, require, AccountId};
+
+use crate::*;
+
+// 奖杯 id 的前缀, 与数字的迷宫 id 区分
+pub const TROPHY_ID_PREFIX: &str = "trophy-";
+// 单次查询的奖杯数量上限
+pub const MAX_TROPHIES_LIMIT: u64 = 100;
+
+/// 解开迷宫后获得的奖杯, 与账户绑定, 不能转移或授权 (NEP-393 soulbound)
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Trophy {
+    pub token_id: String,
+    pub owner_id: AccountId,
+    pub maze_id: TokenId,
+    pub creator_id: Option<AccountId>,
+    // 解开迷宫时的区块高度
+    pub block_height: U64,
+    // 解开前迷宫被猜测的次数, 包括猜中的这一次
+    pub attempts: u32,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn sbt_token(&self, token_id: String) -> Option<Trophy> {
+        trophy_index(&token_id).and_then(|index| self.trophies.get(index))
+    }
+
+    pub fn sbt_supply(&self) -> u64 {
+        self.trophies.len()
+    }
+
+    pub fn sbt_supply_by_owner(&self, account_id: AccountId) -> u64 {
+        self.trophies_per_owner
+            .get(&account_id)
+            .map_or(0, |indexes| indexes.len() as u64)
+    }
+
+    // 按获得顺序查询账户的奖杯
+    pub fn sbt_tokens_by_owner(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<Trophy> {
+        let limit = limit.unwrap_or(MAX_TROPHIES_LIMIT).min(MAX_TROPHIES_LIMIT);
+        let start = usize::try_from(from_index.map_or(0, |index| index.0)).unwrap_or(usize::MAX);
+
+        self.trophies_per_owner
+            .get(&account_id)
+            .map(|indexes| {
+                indexes
+                    .iter()
+                    .skip(start)
+                    .take(limit as usize)
+                    .filter_map(|&index| self.trophies.get(index))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl Contract {
+    // 奖杯不在 `tokens` 中, 这里给出更明确的错误信息
+    pub(crate) fn assert_not_trophy(&self, token_id: &TokenId) {
+        require!(
+            !token_id.starts_with(TROPHY_ID_PREFIX),
+            "Trophies are soulbound and cannot be transferred or approved."
+        );
+    }
+
+    // 给解开迷宫的账户 mint 奖杯, 需要在销毁迷宫前调用
+    pub(crate) fn internal_mint_trophy(&mut self, maze_id: &TokenId, solver_id: &AccountId) {
+        let index = self.trophies.len();
+        let trophy = Trophy {
+            token_id: format!("{}{}", TROPHY_ID_PREFIX, index + 1),
+            owner_id: solver_id.clone(),
+            maze_id: maze_id.clone(),
+            creator_id: self.creator_by_id.get(maze_id).cloned(),
+            block_height: U64(env::block_height()),
+            attempts: self.mazes.get(maze_id).map_or(1, |state| state.attempts),
+        };
+        self.trophies.push(&trophy);
+
+        let mut indexes = self
+            .trophies_per_owner
+            .get(solver_id)
+            .cloned()
+            .unwrap_or_default();
+        indexes.push(index);
+        self.trophies_per_owner.insert(solver_id.clone(), indexes);
+
+        MazesEventLog::new(MazesEventLogVariant::TrophyMinted(vec![trophy])).emit();
+    }
+}
+
+// "trophy-1" -> 0
+fn trophy_index(token_id: &str) -> Option<u64> {
+    token_id
+        .strip_prefix(TROPHY_ID_PREFIX)?
+        .parse::<u64>()
+        .ok()?
+        .checked_sub(1)
+}
@@ -0,0 +1,154 @@
+use near_contract_standards::non_fungible_token::{NonFungibleToken, TokenId};
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LazyOption, UnorderedMap, Vector};
+use near_sdk::store::LookupMap;
+use near_sdk::{env, near_bindgen, AccountId, Gas, Promise};
+
+use crate::*;
+
+// 当前合约状态的版本, 已部署的布局发生变化时需要递增, 并在下面保留上一版本的布局
+pub const CURRENT_STATE_VERSION: u32 = 1;
+
+// 状态版本单独存储, 不存在时为未记录版本的初始布局 (版本 0)
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+
+// `upgrade` 自身及部署代码消耗的 gas, 剩余的 gas 全部留给 `migrate`
+const GAS_FOR_UPGRADE: Gas = Gas(10_000_000_000_000);
+
+/// 版本 0: 最初部署的合约布局
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV0 {
+    pub owner_id: AccountId,
+    pub tokens: NonFungibleToken,
+    pub unique_id: u64,
+    pub secret: LookupMap<TokenId, String>,
+}
+
+/// 链上可能存在的各版本状态, 只保留可以直接升级到当前版本的布局
+pub enum VersionedContract {
+    V0(Box<ContractV0>),
+    Current(Box<Contract>),
+}
+
+impl VersionedContract {
+    /// 按记录的版本读取链上状态
+    pub fn read() -> Self {
+        match read_state_version() {
+            0 => Self::V0(Box::new(
+                env::state_read().expect("Contract state is not initialized."),
+            )),
+            CURRENT_STATE_VERSION => Self::Current(Box::new(
+                env::state_read().expect("Contract state is not initialized."),
+            )),
+            version => panic!("Cannot migrate from state version {}.", version),
+        }
+    }
+}
+
+impl From<VersionedContract> for Contract {
+    fn from(state: VersionedContract) -> Self {
+        match state {
+            VersionedContract::V0(old) => {
+                let mut achievements = Vector::new(StorageKey::Achievements);
+                achievements.extend(default_achievements());
+                let supply = old.tokens.owner_by_id.len();
+
+                Self {
+                    owner_id: old.owner_id,
+                    tokens: old.tokens,
+                    unique_id: old.unique_id,
+                    secret: old.secret,
+                    // 旧 NFT 的图片不会补录到索引中
+                    media_index: LookupMap::new(StorageKey::MediaIndex),
+                    duplicate_policy: DuplicatePolicy::Reject,
+                    royalties: LookupMap::new(StorageKey::Royalties),
+                    metadata: LazyOption::new(
+                        StorageKey::Metadata,
+                        Some(&default_contract_metadata()),
+                    ),
+                    pending_owner_id: None,
+                    roles: LookupMap::new(StorageKey::Roles),
+                    paused: PauseState::default(),
+                    admin_timelock: DEFAULT_ADMIN_TIMELOCK,
+                    admin_actions: Vector::new(StorageKey::AdminActions),
+                    wrapped_nfts: LookupMap::new(StorageKey::WrappedNfts),
+                    bounty_tokens: UnorderedMap::new(StorageKey::BountyTokens),
+                    bounties: LookupMap::new(StorageKey::Bounties),
+                    bounty_claims: LookupMap::new(StorageKey::BountyClaims),
+                    creator_by_id: LookupMap::new(StorageKey::CreatorById),
+                    tokens_per_creator: LookupMap::new(StorageKey::TokensPerCreator),
+                    mazes: LookupMap::new(StorageKey::Mazes),
+                    ft: FungibleToken::new(StorageKey::FungibleToken),
+                    ft_metadata: LazyOption::new(
+                        StorageKey::FtMetadata,
+                        Some(&default_ft_metadata()),
+                    ),
+                    reward_config: RewardConfig::default(),
+                    trophies: Vector::new(StorageKey::Trophies),
+                    trophies_per_owner: LookupMap::new(StorageKey::TrophiesPerOwner),
+                    player_stats: LookupMap::new(StorageKey::PlayerStats),
+                    achievements,
+                    awarded_achievements: LookupMap::new(StorageKey::AwardedAchievements),
+                    // 旧迷宫需要通过 `reindex_mazes` 补录到 maze_index, bounty_index 和 owner_index 中
+                    maze_index: TreeMap::new(StorageKey::MazeIndex),
+                    bounty_index: TreeMap::new(StorageKey::BountyIndex),
+                    solved_mazes: TreeMap::new(StorageKey::SolvedMazes),
+                    // 旧版本只能从 id 和当前数量推算 mint 及销毁数量
+                    stats: ContractStats {
+                        minted: old.unique_id,
+                        burned: old.unique_id - supply,
+                        ..Default::default()
+                    },
+                    attempts_by_account: LookupMap::new(StorageKey::AttemptsByAccount),
+                    owner_index: TreeMap::new(StorageKey::OwnerIndex),
+                    hints: LookupMap::new(StorageKey::Hints),
+                    secret_commitments: LookupMap::new(StorageKey::SecretCommitments),
+                    solved_ids: LookupMap::new(StorageKey::SolvedIds),
+                }
+            }
+            VersionedContract::Current(contract) => *contract,
+        }
+    }
+}
+
+pub(crate) fn read_state_version() -> u32 {
+    env::storage_read(STATE_VERSION_KEY)
+        .map(|bytes| u32::try_from_slice(&bytes).expect("Cannot read state version."))
+        .unwrap_or_default()
+}
+
+pub(crate) fn write_state_version(version: u32) {
+    env::storage_write(STATE_VERSION_KEY, &version.try_to_vec().unwrap());
+}
+
+#[near_bindgen]
+impl Contract {
+    /// 部署新代码后升级链上状态, 已是当前版本时不做修改
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let contract = Contract::from(VersionedContract::read());
+        write_state_version(CURRENT_STATE_VERSION);
+        contract
+    }
+
+    /// 合约所有者部署新代码 (以原始字节作为调用参数), 并在同一批次中调用 `migrate`
+    pub fn upgrade(&self) -> Promise {
+        self.assert_owner();
+        let code = env::input().expect("Missing contract code.");
+        let gas_for_migrate = env::prepaid_gas()
+            .0
+            .checked_sub(env::used_gas().0)
+            .and_then(|gas| gas.checked_sub(GAS_FOR_UPGRADE.0))
+            .map(Gas)
+            .expect("Not enough gas for migrate.");
+
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call("migrate".to_string(), vec![], 0, gas_for_migrate)
+    }
+
+    pub fn view_state_version(&self) -> u32 {
+        read_state_version()
+    }
+}
@@ -0,0 +1,247 @@
+use near_contract_standards::non_fungible_token::core::NonFungibleTokenReceiver;
+use near_contract_standards::non_fungible_token::metadata::TokenMetadata;
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{Base64VecU8, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{
+    env, ext_contract, near_bindgen, require, serde_json, AccountId, Gas, Promise, PromiseOrValue,
+    PromiseResult,
+};
+
+use crate::*;
+
+// 未指定过期时间时, 包装的迷宫 30 天后过期
+pub const DEFAULT_WRAP_DURATION: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+// 调用外部合约 `nft_transfer` 的 gas
+const GAS_FOR_NFT_TRANSFER: Gas = Gas(20_000_000_000_000);
+// `on_wrapped_nft_transfer` 回调的 gas
+const GAS_FOR_WRAPPED_NFT_CALLBACK: Gas = Gas(10_000_000_000_000);
+
+// 外部 NEP-171 合约的转移接口
+#[ext_contract(ext_nft)]
+pub trait ExternalNft {
+    fn nft_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    );
+}
+
+/// 通过 `nft_transfer_call` 包装外部 NFT 时 `msg` 的内容
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WrapMsg {
+    // `salt` 与 secret 拼接后的 sha256, 链上不会出现 secret 明文
+    pub secret_hash: Base64VecU8,
+    pub salt: String,
+    pub metadata: TokenMetadata,
+    // 过期时间 (纳秒时间戳), 不传时为 `DEFAULT_WRAP_DURATION` 之后
+    pub expires_at: Option<U64>,
+    pub memo: Option<String>,
+    // 迷宫难度, 只能由合约所有者或 Minter 通过 `rate_maze` 设置, 必须不传
+    pub difficulty: Option<u8>,
+}
+
+/// 迷宫托管的外部 NFT
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WrappedNft {
+    pub nft_contract_id: AccountId,
+    pub nft_token_id: TokenId,
+    // 转入 NFT 的账户, 过期后可以取回
+    pub creator_id: AccountId,
+    pub expires_at: U64,
+    // 已发放给的账户, 外部转移失败时由该账户调用 `claim_wrapped_nft` 重试
+    pub receiver_id: Option<AccountId>,
+}
+
+// 外部 NFT 合约通过 `nft_transfer_call` 转入 NFT, 创建一个托管该 NFT 的迷宫
+// 出错时 panic, 外部合约会把 NFT 退还给原所有者
+#[near_bindgen]
+impl NonFungibleTokenReceiver for Contract {
+    fn nft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        previous_owner_id: AccountId,
+        token_id: TokenId,
+        msg: String,
+    ) -> PromiseOrValue<bool> {
+        // `sender_id` 可能是被授权的账户, 以原所有者作为创建者
+        let _ = sender_id;
+        self.assert_not_paused(PauseFeature::Mint);
+        let nft_contract_id = env::predecessor_account_id();
+        require!(
+            nft_contract_id != env::current_account_id(),
+            "Cannot wrap a maze from this contract."
+        );
+        let msg: WrapMsg = serde_json::from_str(&msg).expect("Invalid wrap message.");
+        let now = env::block_timestamp();
+        let expires_at = msg
+            .expires_at
+            .map_or(now + DEFAULT_WRAP_DURATION, |expires_at| expires_at.0);
+        require!(expires_at > now, "Expiration must be in the future.");
+        require!(
+            msg.difficulty.is_none(),
+            "Only contract owner or Minter can set difficulty."
+        );
+        require!(
+            msg.secret_hash.0.len() == 32,
+            "secret_hash must be a sha256 hash."
+        );
+        require!(!msg.salt.is_empty(), "salt cannot be empty.");
+
+        let maze_id = self.next_id().to_string();
+        self.internal_mint(
+            &previous_owner_id,
+            &maze_id,
+            &msg.metadata,
+            msg.memo,
+            MazeSecret::Commitment(SecretCommitment {
+                hash: msg.secret_hash.0,
+                salt: msg.salt,
+            }),
+            None,
+            1,
+        );
+        self.wrapped_nfts.insert(
+            maze_id.clone(),
+            WrappedNft {
+                nft_contract_id: nft_contract_id.clone(),
+                nft_token_id: token_id.clone(),
+                creator_id: previous_owner_id.clone(),
+                expires_at: U64(expires_at),
+                receiver_id: None,
+            },
+        );
+
+        MazesEventLog::new(MazesEventLogVariant::NftWrapped(vec![WrappedNftLog {
+            account_id: previous_owner_id,
+            token_id: maze_id,
+            nft_contract_id,
+            nft_token_id: token_id,
+        }]))
+        .emit();
+        PromiseOrValue::Value(false)
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    // 迷宫过期后, 创建者销毁迷宫并取回托管的 NFT, 赏金退还给出资账户
+    pub fn unwrap_maze(&mut self, token_id: TokenId) -> Promise {
+        self.assert_not_paused(PauseFeature::Guess);
+        let wrapped = self
+            .wrapped_nfts
+            .get(&token_id)
+            .expect("Maze does not wrap an NFT.");
+        require!(
+            env::predecessor_account_id() == wrapped.creator_id,
+            "Only the creator can unwrap the maze."
+        );
+        require!(
+            wrapped.receiver_id.is_none(),
+            "The wrapped NFT has already been released."
+        );
+        require!(
+            env::block_timestamp() >= wrapped.expires_at.0,
+            "The maze has not expired yet."
+        );
+        let creator_id = wrapped.creator_id.clone();
+
+        if self.tokens.owner_by_id.contains_key(&token_id) {
+            self.internal_burn(&token_id, None);
+        }
+        self.internal_refund_bounty(&token_id);
+        self.internal_release_wrapped_nft(&token_id, &creator_id)
+            .unwrap()
+    }
+
+    // 外部转移失败后, 获得 NFT 的账户重新领取
+    pub fn claim_wrapped_nft(&mut self, token_id: TokenId) -> Promise {
+        let wrapped = self
+            .wrapped_nfts
+            .get(&token_id)
+            .expect("Maze does not wrap an NFT.");
+        let receiver_id = wrapped
+            .receiver_id
+            .clone()
+            .expect("The wrapped NFT has not been released.");
+        require!(
+            env::predecessor_account_id() == receiver_id,
+            "Only the receiver can claim the wrapped NFT."
+        );
+        self.internal_release_wrapped_nft(&token_id, &receiver_id)
+            .unwrap()
+    }
+
+    // 外部 `nft_transfer` 的回调, 成功后删除托管记录并返回 `true`
+    #[private]
+    pub fn on_wrapped_nft_transfer(&mut self, token_id: TokenId) -> bool {
+        let wrapped = match self.wrapped_nfts.get(&token_id) {
+            Some(wrapped) => wrapped.clone(),
+            None => return false,
+        };
+        let success = env::promise_results_count() == 1
+            && matches!(env::promise_result(0), PromiseResult::Successful(_));
+        let log = WrappedNftLog {
+            account_id: wrapped.receiver_id.unwrap_or(wrapped.creator_id),
+            token_id: token_id.clone(),
+            nft_contract_id: wrapped.nft_contract_id,
+            nft_token_id: wrapped.nft_token_id,
+        };
+
+        if success {
+            self.wrapped_nfts.remove(&token_id);
+            MazesEventLog::new(MazesEventLogVariant::NftUnwrapped(vec![log])).emit();
+        } else {
+            MazesEventLog::new(MazesEventLogVariant::NftUnwrapFailed(vec![log])).emit();
+        }
+        success
+    }
+
+    pub fn view_wrapped_nft(&self, token_id: TokenId) -> Option<WrappedNft> {
+        self.wrapped_nfts.get(&token_id).cloned()
+    }
+}
+
+impl Contract {
+    // 过期的迷宫不能再被猜测
+    pub(crate) fn assert_not_expired(&self, token_id: &TokenId) {
+        if let Some(wrapped) = self.wrapped_nfts.get(token_id) {
+            require!(
+                env::block_timestamp() < wrapped.expires_at.0,
+                "The maze has expired."
+            );
+        }
+    }
+
+    // 把迷宫托管的 NFT 转给 `receiver_id`, 迷宫没有托管 NFT 时返回 `None`
+    // 托管记录保留到回调确认转移成功为止
+    pub(crate) fn internal_release_wrapped_nft(
+        &mut self,
+        token_id: &TokenId,
+        receiver_id: &AccountId,
+    ) -> Option<Promise> {
+        let wrapped = self.wrapped_nfts.get_mut(token_id)?;
+        wrapped.receiver_id = Some(receiver_id.clone());
+
+        let promise = ext_nft::ext(wrapped.nft_contract_id.clone())
+            .with_attached_deposit(1)
+            .with_static_gas(GAS_FOR_NFT_TRANSFER)
+            .nft_transfer(
+                receiver_id.clone(),
+                wrapped.nft_token_id.clone(),
+                None,
+                Some(format!("Prize of maze {}", token_id)),
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_WRAPPED_NFT_CALLBACK)
+                    .on_wrapped_nft_transfer(token_id.clone()),
+            );
+        Some(promise)
+    }
+}